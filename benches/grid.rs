@@ -0,0 +1,137 @@
+//! Compares the flat grid storage and the borrowed grid view with the nested
+//! `Vec<Vec<char>>` layout the grid used before. Run with `cargo bench --bench grid`.
+
+use aoc2024::bench::{measure, Timings};
+use aoc2024::utils::grid_view::GridView;
+use aoc2024::utils::square_grid::{ByteGrid, SquareCharacterGrid};
+use std::hint::black_box;
+
+const SIZE: usize = 141;
+const WARMUP: usize = 5;
+const RUNS: usize = 200;
+
+/// The previous layout: one allocation per row and two bounds checks per lookup.
+struct NestedGrid {
+    grid: Vec<Vec<char>>,
+}
+
+impl NestedGrid {
+    fn new(input: &str) -> Self {
+        NestedGrid {
+            grid: input.lines().map(|line| line.chars().collect()).collect(),
+        }
+    }
+
+    fn get(&self, x: i32, y: i32) -> Option<char> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+        let (x, y) = (x as usize, y as usize);
+        if x >= self.grid[0].len() || y >= self.grid.len() {
+            return None;
+        }
+        Some(self.grid[y][x])
+    }
+}
+
+/// A garden-like input: mostly '.', some walls and a few plant types.
+fn input() -> String {
+    let mut state: u32 = 12345;
+    let mut input = String::with_capacity(SIZE * (SIZE + 1));
+    for _ in 0..SIZE {
+        for _ in 0..SIZE {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            input.push(b".#AB"[(state >> 16) as usize % 4] as char);
+        }
+        input.push('\n');
+    }
+    input
+}
+
+/// Count cells that have the same value as the cell to their right and below,
+/// the access pattern of the region and edge scans.
+fn same_neighbors<T: PartialEq>(get: impl Fn(i32, i32) -> Option<T>) -> usize {
+    let mut count = 0;
+    for y in 0..SIZE as i32 {
+        for x in 0..SIZE as i32 {
+            let c = get(x, y);
+            count += usize::from(get(x + 1, y) == c) + usize::from(get(x, y + 1) == c);
+        }
+    }
+    count
+}
+
+/// [same_neighbors] on the row-major cells of a grid.
+fn linear_same_neighbors(cells: &[u8]) -> usize {
+    let mut count = 0;
+    for (index, c) in cells.iter().enumerate() {
+        let right = index % SIZE + 1 < SIZE && cells[index + 1] == *c;
+        let below = cells.get(index + SIZE) == Some(c);
+        count += usize::from(right) + usize::from(below);
+    }
+    count
+}
+
+fn report(name: &str, timings: Timings) {
+    println!(
+        "{:<28} min {:>10.2?}  median {:>10.2?}  max {:>10.2?}",
+        name, timings.min, timings.median, timings.max
+    );
+}
+
+fn main() {
+    let input = input();
+    let nested = NestedGrid::new(&input);
+    let chars: SquareCharacterGrid<i32> = SquareCharacterGrid::new(&input);
+    let bytes: ByteGrid<i32> = ByteGrid::from_ascii(&input);
+    let view: GridView<i32> = GridView::new(&input);
+    let expected = same_neighbors(|x, y| nested.get(x, y));
+    assert_eq!(same_neighbors(|x, y| chars.get(x, y)), expected);
+    assert_eq!(same_neighbors(|x, y| bytes.get(x, y)), expected);
+    assert_eq!(linear_same_neighbors(bytes.cells()), expected);
+    assert_eq!(same_neighbors(|x, y| view.get(x, y)), expected);
+
+    println!("{}x{} grid, {} runs", SIZE, SIZE, RUNS);
+    let bench = |name: &str, f: &dyn Fn() -> usize| {
+        let timings = measure(WARMUP, RUNS, || {
+            black_box(f());
+        });
+        report(name, timings.expect("at least one run"));
+    };
+
+    bench("construct Vec<Vec<char>>", &|| {
+        NestedGrid::new(black_box(&input)).grid.len()
+    });
+    bench("construct Grid<char>", &|| {
+        SquareCharacterGrid::<i32>::new(black_box(&input))
+            .cells()
+            .len()
+    });
+    bench("construct Grid<u8>", &|| {
+        ByteGrid::<i32>::from_ascii(black_box(&input)).cells().len()
+    });
+    bench("construct GridView", &|| {
+        GridView::<i32>::new(black_box(&input)).size().0 as usize
+    });
+
+    bench("neighbors Vec<Vec<char>>", &|| {
+        let nested = black_box(&nested);
+        same_neighbors(|x, y| nested.get(x, y))
+    });
+    bench("neighbors Grid<char>", &|| {
+        let chars = black_box(&chars);
+        same_neighbors(|x, y| chars.get(x, y))
+    });
+    bench("neighbors Grid<u8>", &|| {
+        let bytes = black_box(&bytes);
+        same_neighbors(|x, y| bytes.get(x, y))
+    });
+    bench("neighbors GridView", &|| {
+        let view = black_box(&view);
+        same_neighbors(|x, y| view.get(x, y))
+    });
+    // With linear indices, scans need no coordinate checks at all.
+    bench("neighbors Grid<u8> linear", &|| {
+        linear_same_neighbors(black_box(&bytes).cells())
+    });
+}
@@ -0,0 +1,314 @@
+//! Known-good answers, used by the `verify` command.
+//!
+//! Answers live in `answers.txt` in the crate root, one per line:
+//!
+//! ```text
+//! <day> <part> <input> <answer>
+//! ```
+//!
+//! Fields may be separated by any amount of whitespace, so lines can be
+//! aligned. The answer is the rest of the line and may contain spaces.
+//! Empty lines and lines starting with `#` are ignored.
+
+use crate::error::Error;
+use crate::inputs;
+use crate::registry::Day;
+use crate::solution::Answer;
+use crate::utils::crate_root;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// The expected answer for one part of one day on one input file.
+#[derive(Debug, PartialEq)]
+pub struct ExpectedAnswer {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: String,
+}
+
+/// Outcome of checking a computed answer against the answers file.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+pub struct Answers {
+    expected: Vec<ExpectedAnswer>,
+}
+
+impl Answers {
+    /// Load the answers file from the crate root.
+    pub fn load() -> Result<Self, Error> {
+        let path = crate_root().join(ANSWERS_FILE);
+        let text = fs::read_to_string(&path).map_err(|source| match source.kind() {
+            ErrorKind::NotFound => Error::MissingInput {
+                path: path.clone(),
+                day: None,
+            },
+            _ => Error::UnreadableInput {
+                path: path.clone(),
+                day: None,
+                source,
+            },
+        })?;
+        Self::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut expected = vec![];
+        for (line_index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || {
+                Error::Message(format!(
+                    "{}:{}: invalid line '{}'",
+                    ANSWERS_FILE,
+                    line_index + 1,
+                    line
+                ))
+            };
+            let (day, rest) = next_field(line).ok_or_else(error)?;
+            let (part, rest) = next_field(rest).ok_or_else(error)?;
+            let (input, answer) = next_field(rest).ok_or_else(error)?;
+            let day = day.parse().map_err(|_| error())?;
+            let part = part.parse().map_err(|_| error())?;
+            let input = input.to_string();
+            let answer = answer.trim().to_string();
+            if answer.is_empty() {
+                return Err(error());
+            }
+            expected.push(ExpectedAnswer {
+                day,
+                part,
+                input,
+                answer,
+            });
+        }
+        Ok(Answers { expected })
+    }
+
+    /// Get the expected answer for a day, part and input name.
+    pub fn get(&self, day: u8, part: u8, input: &str) -> Option<&str> {
+        self.expected
+            .iter()
+            .find(|e| e.day == day && e.part == part && e.input == input)
+            .map(|e| e.answer.as_str())
+    }
+
+    /// Check a computed answer against the expected one.
+    pub fn check(&self, day: u8, part: u8, input: &str, answer: &Answer) -> Verdict {
+        match self.get(day, part, input) {
+            None => Verdict::Missing,
+            Some(expected) if expected == answer.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+/// What checking one part of one day on one input found.
+#[derive(Debug)]
+pub enum Outcome {
+    /// The part was solved, and its answer checked against the answers file.
+    Checked { answer: Answer, verdict: Verdict },
+    /// Solving the part failed.
+    Failed(Error),
+}
+
+/// One row of a [Report].
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: u8,
+    pub input_name: String,
+    pub outcome: Outcome,
+}
+
+/// The result of [verify], one row per solved or failed part.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    pub fn passed(&self) -> usize {
+        self.count(|outcome| {
+            matches!(
+                outcome,
+                Outcome::Checked {
+                    verdict: Verdict::Pass,
+                    ..
+                }
+            )
+        })
+    }
+
+    /// Parts that failed to solve or whose answer does not match.
+    pub fn failed(&self) -> usize {
+        self.count(|outcome| {
+            matches!(
+                outcome,
+                Outcome::Failed(_)
+                    | Outcome::Checked {
+                        verdict: Verdict::Fail { .. },
+                        ..
+                    }
+            )
+        })
+    }
+
+    /// Solved parts without an expected answer.
+    pub fn missing(&self) -> usize {
+        self.count(|outcome| {
+            matches!(
+                outcome,
+                Outcome::Checked {
+                    verdict: Verdict::Missing,
+                    ..
+                }
+            )
+        })
+    }
+
+    fn count(&self, is_counted: impl Fn(&Outcome) -> bool) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| is_counted(&check.outcome))
+            .count()
+    }
+}
+
+/// Run all `days` on all of their input files in `dir` and check the answers.
+/// Parts that are not solved are left out. Fails if an input cannot be read.
+pub fn verify(days: &[Day], dir: &Path, answers: &Answers) -> Result<Report, Error> {
+    let mut report = Report::default();
+    for day in days {
+        for input_file in inputs::discover_in(dir, day.number) {
+            let input = input_file.read()?;
+            for part in [1, 2] {
+                let outcome = match day.solution.solve(part, &input) {
+                    Ok(Some(answer)) => {
+                        let verdict = answers.check(day.number, part, &input_file.name, &answer);
+                        Outcome::Checked { answer, verdict }
+                    }
+                    Ok(None) => continue,
+                    Err(error) => Outcome::Failed(error),
+                };
+                report.checks.push(Check {
+                    day: day.number,
+                    part,
+                    input_name: input_file.name.clone(),
+                    outcome,
+                });
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Split the first whitespace separated field off the line, returning the
+/// field and the rest of the line after the whitespace.
+fn next_field(line: &str) -> Option<(&str, &str)> {
+    let (field, rest) = line.trim_start().split_once(char::is_whitespace)?;
+    Some((field, rest.trim_start()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day11::Day11;
+    use crate::solution::Solution;
+    use crate::utils::TempPath;
+
+    /// Counts the lines of the input, part 2 is not solved.
+    struct LineCount;
+
+    impl Solution for LineCount {
+        fn part1(&self, input: &str) -> Answer {
+            input.lines().count().into()
+        }
+    }
+
+    #[test]
+    fn test_parse_and_check() {
+        let answers = Answers::parse("# comment\n\n6 1 day6-test 41\n6 2 day6-test 6\n").unwrap();
+        assert_eq!(answers.get(6, 1, "day6-test"), Some("41"));
+        assert_eq!(answers.check(6, 1, "day6-test", &41.into()), Verdict::Pass);
+        assert_eq!(
+            answers.check(6, 2, "day6-test", &7.into()),
+            Verdict::Fail {
+                expected: "6".to_string()
+            }
+        );
+        assert_eq!(answers.check(6, 1, "day6", &41.into()), Verdict::Missing);
+    }
+
+    #[test]
+    fn test_parse_aligned() {
+        let answers = Answers::parse("6  1 day6\t41\n12 2 day12   A B \n").unwrap();
+        assert_eq!(answers.get(6, 1, "day6"), Some("41"));
+        assert_eq!(answers.get(12, 2, "day12"), Some("A B"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(Answers::parse("6 x day6 41").is_err());
+        assert!(Answers::parse("6 1 day6").is_err());
+        assert!(Answers::parse("6 1 day6 ").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let days = [
+            Day {
+                number: 11,
+                solution: &Day11,
+            },
+            Day {
+                number: 99,
+                solution: &LineCount,
+            },
+        ];
+        let dir = TempPath::new("verify");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day11"), "not a number").unwrap();
+        fs::write(dir.join("day99"), "a\nb\n").unwrap();
+        fs::write(dir.join("day99-test-a"), "a\n").unwrap();
+        fs::write(dir.join("day99-test-b"), "").unwrap();
+        let answers = Answers::parse("99 1 day99 2\n99 1 day99-test-a 5\n").unwrap();
+
+        let report = verify(&days, &dir, &answers).unwrap();
+        let rows: Vec<_> = report
+            .checks
+            .iter()
+            .map(|check| (check.day, check.part, check.input_name.as_str()))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                (11, 1, "day11"),
+                (11, 2, "day11"),
+                (99, 1, "day99"),
+                (99, 1, "day99-test-a"),
+                (99, 1, "day99-test-b")
+            ]
+        );
+        assert!(matches!(
+            report.checks[0].outcome,
+            Outcome::Failed(Error::Parse(_))
+        ));
+        assert_eq!(
+            (report.passed(), report.failed(), report.missing()),
+            (1, 3, 1)
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Simple wall clock benchmarks for solution parts.
+
+use crate::solution::Timing;
+use std::time::{Duration, Instant};
+
+/// Wall time statistics over a number of runs.
+#[derive(Debug, PartialEq)]
+pub struct Timings {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timings {
+    /// Summarize a list of measured durations. Returns `None` for an empty list.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        samples.sort();
+        let runs = samples.len();
+        Some(Timings {
+            runs,
+            min: *samples.first()?,
+            median: samples[runs / 2],
+            max: *samples.last()?,
+        })
+    }
+}
+
+/// Call `f` `warmup` times without measuring, then measure `runs` calls.
+pub fn measure<F: FnMut()>(warmup: usize, runs: usize, mut f: F) -> Option<Timings> {
+    for _ in 0..warmup {
+        f();
+    }
+    let mut samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        f();
+        samples.push(start.elapsed());
+    }
+    Timings::from_samples(samples)
+}
+
+/// Parse and solve time statistics of a part, see [measure_parts].
+#[derive(Debug, PartialEq)]
+pub struct PartTimings {
+    /// `None` if the solution does not parse its input separately.
+    pub parse: Option<Timings>,
+    pub solve: Timings,
+}
+
+/// Like [measure], but `f` times itself, so parsing and solving are measured
+/// separately. Stops at the first error of `f`.
+pub fn measure_parts<E, F: FnMut() -> Result<Timing, E>>(
+    warmup: usize,
+    runs: usize,
+    mut f: F,
+) -> Result<Option<PartTimings>, E> {
+    for _ in 0..warmup {
+        f()?;
+    }
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut solve_samples = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let timing = f()?;
+        parse_samples.extend(timing.parse);
+        solve_samples.push(timing.solve);
+    }
+    Ok(
+        Timings::from_samples(solve_samples).map(|solve| PartTimings {
+            parse: Timings::from_samples(parse_samples),
+            solve,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_samples() {
+        let ms = Duration::from_millis;
+        let timings = Timings::from_samples(vec![ms(5), ms(1), ms(3)]).unwrap();
+        assert_eq!(
+            timings,
+            Timings {
+                runs: 3,
+                min: ms(1),
+                median: ms(3),
+                max: ms(5)
+            }
+        );
+        assert_eq!(Timings::from_samples(vec![]), None);
+    }
+
+    #[test]
+    fn test_measure_counts_calls() {
+        let mut calls = 0;
+        let timings = measure(2, 5, || calls += 1).unwrap();
+        assert_eq!(calls, 7);
+        assert_eq!(timings.runs, 5);
+    }
+
+    #[test]
+    fn test_measure_parts() {
+        let ms = Duration::from_millis;
+        let mut calls = 0;
+        let timings = measure_parts::<(), _>(1, 3, || {
+            calls += 1;
+            Ok(Timing {
+                parse: Some(ms(calls)),
+                solve: ms(10 * calls),
+            })
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(calls, 4);
+        assert_eq!(timings.parse.unwrap().min, ms(2));
+        assert_eq!(timings.solve.max, ms(40));
+
+        let timings = measure_parts::<(), _>(0, 2, || {
+            Ok(Timing {
+                parse: None,
+                solve: ms(1),
+            })
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(timings.parse, None);
+        assert_eq!(measure_parts(0, 1, || Err("failed")), Err("failed"));
+    }
+}
@@ -0,0 +1,106 @@
+//! A cache for computed answers, so slow days do not have to be solved again.
+//!
+//! Caching is opt-in per day: only solutions that return a
+//! [crate::solution::Solution::version] are cached. An entry is found by a
+//! hash of the day, the part, the solution version and the input contents,
+//! so changing the input or bumping the version makes the old entry unused.
+
+use crate::solution::Answer;
+use crate::utils::crate_root;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Directory in the crate root with the cached answers.
+pub const CACHE_DIR: &str = ".answer-cache";
+
+pub struct AnswerCache {
+    dir: PathBuf,
+}
+
+impl AnswerCache {
+    /// The cache in [CACHE_DIR] of the crate root.
+    pub fn open() -> Self {
+        Self::in_dir(&crate_root().join(CACHE_DIR))
+    }
+
+    pub fn in_dir(dir: &Path) -> Self {
+        AnswerCache {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    fn entry_path(&self, day: u8, part: u8, version: u32, input: &str) -> PathBuf {
+        let key = fingerprint(day, part, version, input);
+        self.dir
+            .join(format!("day{}-part{}-{:016x}", day, part, key))
+    }
+
+    /// The cached answer, if there is one. Unreadable entries count as missing.
+    pub fn get(&self, day: u8, part: u8, version: u32, input: &str) -> Option<Answer> {
+        let entry = fs::read_to_string(self.entry_path(day, part, version, input)).ok()?;
+        match entry.strip_suffix('\n')?.split_once(' ')? {
+            ("number", n) => n.parse().ok().map(Answer::Number),
+            ("text", text) => Some(Answer::Text(text.to_string())),
+            _ => None,
+        }
+    }
+
+    pub fn put(
+        &self,
+        day: u8,
+        part: u8,
+        version: u32,
+        input: &str,
+        answer: &Answer,
+    ) -> io::Result<()> {
+        let entry = match answer {
+            Answer::Number(n) => format!("number {}\n", n),
+            Answer::Text(text) => format!("text {}\n", text),
+        };
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.entry_path(day, part, version, input), entry)
+    }
+}
+
+/// 64 bit FNV-1a hash of the cache key. Unlike the hasher of the standard
+/// library, this is guaranteed to stay the same between Rust versions.
+fn fingerprint(day: u8, part: u8, version: u32, input: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    let key = [&[day, part][..], &version.to_le_bytes(), input.as_bytes()];
+    key.iter()
+        .flat_map(|bytes| bytes.iter())
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::TempPath;
+
+    #[test]
+    fn test_fingerprint() {
+        // FNV-1a of six zero bytes, the cache key must not change between builds.
+        assert_eq!(fingerprint(0, 0, 0, ""), 0xd7e4fcfa299d713d);
+        assert_ne!(fingerprint(6, 1, 0, "a"), fingerprint(6, 2, 0, "a"));
+        assert_ne!(fingerprint(6, 1, 0, "a"), fingerprint(6, 1, 1, "a"));
+        assert_ne!(fingerprint(6, 1, 0, "a"), fingerprint(6, 1, 0, "b"));
+    }
+
+    #[test]
+    fn test_get_and_put() {
+        let dir = TempPath::new("cache");
+        let cache = AnswerCache::in_dir(&dir);
+        assert_eq!(cache.get(6, 2, 1, "input"), None);
+        cache.put(6, 2, 1, "input", &Answer::Number(1915)).unwrap();
+        cache.put(6, 1, 1, "input", &Answer::from("ABC")).unwrap();
+        assert_eq!(cache.get(6, 2, 1, "input"), Some(Answer::Number(1915)));
+        assert_eq!(cache.get(6, 1, 1, "input"), Some(Answer::from("ABC")));
+        // A changed input or a new version of the solution misses the cache.
+        assert_eq!(cache.get(6, 2, 1, "changed input"), None);
+        assert_eq!(cache.get(6, 2, 2, "input"), None);
+    }
+}
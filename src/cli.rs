@@ -0,0 +1,456 @@
+//! Command line parsing for the puzzle runner.
+
+use aoc2024::inputs::InputSelection;
+use aoc2024::output::Format;
+use aoc2024::watch::DEFAULT_INTERVAL;
+use std::iter::Peekable;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+Usage:
+    aoc2024 run <day> [--part <1|2>] [<inputs>] [--format <format>] [--no-cache]
+    aoc2024 run --all [--part <1|2>] [--threads <n>] [--format <format>] [--no-cache]
+    aoc2024 verify
+    aoc2024 bench <day> [--part <1|2>] [<inputs>] [--runs <n>] [--warmup <n>]
+    aoc2024 watch <day> [--part <1|2>] [<inputs>] [--interval <ms>]
+    aoc2024 new <day>
+    aoc2024 fetch <day> [--base-url <url>]
+    aoc2024 extract <day> <page.html>
+
+Commands:
+    run     Run a day. Runs both parts unless --part is given.
+            With --all, runs every day in parallel and prints a summary table.
+            --format json or csv prints one record per day, part and input.
+            Days with a solution version cache their answers in .answer-cache,
+            --no-cache solves them again.
+    verify  Run every day on all its inputs and compare against answers.txt.
+    bench   Time a day over several runs and report min, median and max,
+            with parsing and solving timed separately where possible.
+            Defaults to 10 runs and 2 warm-up runs.
+    watch   Run a day, then run it again whenever one of its inputs changes.
+            Polls the input files every 500 ms unless --interval is given.
+    new     Create src/dayN.rs and empty input files for a new day and register it.
+    fetch   Download the real input of a day into the input directory, unless it
+            is there already. Needs curl on the PATH for https URLs and a session
+            token in $AOC_SESSION or .aoc-session.
+            The base URL defaults to $AOC_BASE_URL or https://adventofcode.com.
+    extract Write the examples of a saved puzzle page to dayN-test-a, dayN-test-b, ...
+            and the answers emphasized on the page to dayN-test-a.answer, ...
+
+Inputs (default: the real input, input/dayN):
+    --example         All examples, input/dayN-test and input/dayN-test-*
+    --example <name>  A single example, e.g. 'b' for input/dayN-test-b
+    --input <path>    Any file, or - to read from stdin
+
+Options:
+    --input-dir <dir> Directory with the input files. Defaults to $AOC_INPUT_DIR,
+                      or the input directory in the crate root.";
+
+pub const DEFAULT_RUNS: usize = 10;
+pub const DEFAULT_WARMUP: usize = 2;
+
+/// A parsed command line: global options and the command.
+#[derive(Debug, PartialEq)]
+pub struct Cli {
+    pub input_dir: Option<PathBuf>,
+    pub command: Command,
+}
+
+/// A parsed command.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        day: u8,
+        part: Option<u8>,
+        inputs: InputSelection,
+        format: Format,
+        cache: bool,
+    },
+    RunAll {
+        part: Option<u8>,
+        threads: Option<usize>,
+        format: Format,
+        cache: bool,
+    },
+    Verify,
+    Bench {
+        day: u8,
+        part: Option<u8>,
+        inputs: InputSelection,
+        runs: usize,
+        warmup: usize,
+    },
+    Watch {
+        day: u8,
+        part: Option<u8>,
+        inputs: InputSelection,
+        interval: Duration,
+    },
+    New {
+        day: u8,
+    },
+    Fetch {
+        day: u8,
+        base_url: Option<String>,
+    },
+    Extract {
+        day: u8,
+        page: PathBuf,
+    },
+    Help,
+}
+
+/// Parse the command line arguments, without the program name.
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Cli, String> {
+    // Global options may appear anywhere, so take them out first.
+    let mut input_dir = None;
+    let mut command_args = vec![];
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input-dir" => {
+                let dir = args.next().ok_or("Missing value for --input-dir")?;
+                input_dir = Some(PathBuf::from(dir));
+            }
+            _ => command_args.push(arg),
+        }
+    }
+    let command = parse_command(command_args.into_iter())?;
+    Ok(Cli { input_dir, command })
+}
+
+fn parse_command<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let Some(command) = args.next() else {
+        return Ok(Command::Help);
+    };
+    match command.as_str() {
+        "run" => parse_run(args),
+        "verify" => parse_no_arguments(args, Command::Verify),
+        "bench" => parse_bench(args),
+        "watch" => parse_watch(args),
+        "new" => {
+            let day = args.next().ok_or("Missing day for 'new'")?;
+            let day = parse_number("day", &day)?;
+            parse_no_arguments(args, Command::New { day })
+        }
+        "fetch" => parse_fetch(args),
+        "extract" => {
+            let day = args.next().ok_or("Missing day for 'extract'")?;
+            let day = parse_number("day", &day)?;
+            let page = args.next().ok_or("Missing puzzle page for 'extract'")?;
+            let page = PathBuf::from(page);
+            parse_no_arguments(args, Command::Extract { day, page })
+        }
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        _ => Err(format!("Unknown command '{}'", command)),
+    }
+}
+
+fn parse_run<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.peekable();
+    let day = args.next().ok_or("Missing day for 'run'")?;
+    let day = match day.as_str() {
+        "--all" | "-a" => None,
+        _ => Some(parse_number("day", &day)?),
+    };
+    let mut part = None;
+    let mut inputs = InputSelection::Real;
+    let mut threads = None;
+    let mut format = Format::Text;
+    let mut cache = true;
+    while let Some(arg) = args.next() {
+        if day.is_some() && parse_input_flag(&arg, &mut args, &mut inputs)? {
+            continue;
+        }
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--part" | "-p" => part = Some(parse_number("part", &value()?)?),
+            "--format" | "-f" => format = value()?.parse()?,
+            "--no-cache" => cache = false,
+            "--threads" | "-j" if day.is_none() => {
+                threads = Some(parse_number("thread count", &value()?)?)
+            }
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    match day {
+        Some(day) => Ok(Command::Run {
+            day,
+            part,
+            inputs,
+            format,
+            cache,
+        }),
+        None => Ok(Command::RunAll {
+            part,
+            threads,
+            format,
+            cache,
+        }),
+    }
+}
+
+fn parse_bench<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.peekable();
+    let day = args.next().ok_or("Missing day for 'bench'")?;
+    let day = parse_number("day", &day)?;
+    let mut part = None;
+    let mut inputs = InputSelection::Real;
+    let mut runs = DEFAULT_RUNS;
+    let mut warmup = DEFAULT_WARMUP;
+    while let Some(arg) = args.next() {
+        if parse_input_flag(&arg, &mut args, &mut inputs)? {
+            continue;
+        }
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--part" | "-p" => part = Some(parse_number("part", &value()?)?),
+            "--runs" | "-n" => runs = parse_number("run count", &value()?)?,
+            "--warmup" => warmup = parse_number("warm-up count", &value()?)?,
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    if runs == 0 {
+        return Err("--runs must be at least 1".to_string());
+    }
+    Ok(Command::Bench {
+        day,
+        part,
+        inputs,
+        runs,
+        warmup,
+    })
+}
+
+fn parse_watch<I: Iterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.peekable();
+    let day = args.next().ok_or("Missing day for 'watch'")?;
+    let day = parse_number("day", &day)?;
+    let mut part = None;
+    let mut inputs = InputSelection::Real;
+    let mut interval = DEFAULT_INTERVAL;
+    while let Some(arg) = args.next() {
+        if parse_input_flag(&arg, &mut args, &mut inputs)? {
+            continue;
+        }
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--part" | "-p" => part = Some(parse_number("part", &value()?)?),
+            "--interval" => interval = Duration::from_millis(parse_number("interval", &value()?)?),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(Command::Watch {
+        day,
+        part,
+        inputs,
+        interval,
+    })
+}
+
+fn parse_fetch<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let day = args.next().ok_or("Missing day for 'fetch'")?;
+    let day = parse_number("day", &day)?;
+    let mut base_url = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--base-url" => {
+                base_url = Some(args.next().ok_or("Missing value for --base-url")?);
+            }
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    Ok(Command::Fetch { day, base_url })
+}
+
+/// Handle `--example [name]` and `--input <path>`. Returns `false` for any other argument.
+fn parse_input_flag<I: Iterator<Item = String>>(
+    arg: &str,
+    args: &mut Peekable<I>,
+    inputs: &mut InputSelection,
+) -> Result<bool, String> {
+    match arg {
+        "--example" | "-e" => {
+            // The example name is optional, so only take the next argument if it is not a flag.
+            let name = args.next_if(|next| !next.starts_with('-'));
+            *inputs = InputSelection::Examples(name);
+        }
+        "--input" | "-i" => {
+            let path = args.next().ok_or("Missing value for --input")?;
+            *inputs = InputSelection::Path(PathBuf::from(path));
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn parse_no_arguments<I: Iterator<Item = String>>(
+    mut args: I,
+    command: Command,
+) -> Result<Command, String> {
+    match args.next() {
+        Some(arg) => Err(format!("Unexpected argument '{}'", arg)),
+        None => Ok(command),
+    }
+}
+
+fn parse_number<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid {} '{}', expected a number", name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|arg| arg.to_string())).map(|cli| cli.command)
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            parse(&["run", "12"]),
+            Ok(Command::Run {
+                day: 12,
+                part: None,
+                inputs: InputSelection::Real,
+                format: Format::Text,
+                cache: true
+            })
+        );
+        assert_eq!(
+            parse(&["run", "6", "--part", "2", "--no-cache"]),
+            Ok(Command::Run {
+                day: 6,
+                part: Some(2),
+                inputs: InputSelection::Real,
+                format: Format::Text,
+                cache: false
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_inputs() {
+        let inputs = |args: &[&str]| match parse(args) {
+            Ok(Command::Run { inputs, .. }) => inputs,
+            other => panic!("unexpected parse result {:?}", other),
+        };
+        assert_eq!(
+            inputs(&["run", "12", "--example"]),
+            InputSelection::Examples(None)
+        );
+        assert_eq!(
+            inputs(&["run", "12", "--example", "--part", "1"]),
+            InputSelection::Examples(None)
+        );
+        assert_eq!(
+            inputs(&["run", "12", "--example", "b"]),
+            InputSelection::Examples(Some("b".to_string()))
+        );
+        assert_eq!(
+            inputs(&["run", "12", "--input", "/tmp/garden"]),
+            InputSelection::Path(PathBuf::from("/tmp/garden"))
+        );
+        assert!(parse(&["run", "--all", "--example"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_input_dir() {
+        let args = ["run", "6", "--input-dir", "/srv/aoc", "--input", "-"];
+        assert_eq!(
+            parse_args(args.map(String::from)),
+            Ok(Cli {
+                input_dir: Some(PathBuf::from("/srv/aoc")),
+                command: Command::Run {
+                    day: 6,
+                    part: None,
+                    inputs: InputSelection::Path(PathBuf::from("-")),
+                    format: Format::Text,
+                    cache: true
+                }
+            })
+        );
+        assert!(parse(&["run", "6", "--input-dir"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_run_all() {
+        assert_eq!(
+            parse(&["run", "--all"]),
+            Ok(Command::RunAll {
+                part: None,
+                threads: None,
+                format: Format::Text,
+                cache: true
+            })
+        );
+        assert_eq!(
+            parse(&["run", "--all", "-j", "4", "--part", "1", "--format", "csv"]),
+            Ok(Command::RunAll {
+                part: Some(1),
+                threads: Some(4),
+                format: Format::Csv,
+                cache: true
+            })
+        );
+        assert!(matches!(
+            parse(&["run", "--all", "--no-cache"]),
+            Ok(Command::RunAll { cache: false, .. })
+        ));
+        assert!(parse(&["run", "6", "--threads", "4"]).is_err());
+        assert!(parse(&["run", "--all", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            parse(&["bench", "6", "--part", "2", "--runs", "3", "--example"]),
+            Ok(Command::Bench {
+                day: 6,
+                part: Some(2),
+                inputs: InputSelection::Examples(None),
+                runs: 3,
+                warmup: DEFAULT_WARMUP
+            })
+        );
+        assert!(parse(&["bench", "6", "--runs", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        assert_eq!(
+            parse(&["watch", "12", "--example", "a", "--interval", "100"]),
+            Ok(Command::Watch {
+                day: 12,
+                part: None,
+                inputs: InputSelection::Examples(Some("a".to_string())),
+                interval: Duration::from_millis(100)
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(&["run"]).is_err());
+        assert!(parse(&["run", "six"]).is_err());
+        assert!(parse(&["run", "6", "--part"]).is_err());
+        assert!(parse(&["jump", "6"]).is_err());
+        assert!(parse(&["verify", "6"]).is_err());
+        assert!(parse(&["new"]).is_err());
+        assert_eq!(parse(&["new", "13"]), Ok(Command::New { day: 13 }));
+        assert!(parse(&["extract", "13"]).is_err());
+        assert_eq!(
+            parse(&["fetch", "13", "--base-url", "http://localhost:8080"]),
+            Ok(Command::Fetch {
+                day: 13,
+                base_url: Some("http://localhost:8080".to_string())
+            })
+        );
+        assert_eq!(parse(&[]), Ok(Command::Help));
+    }
+}
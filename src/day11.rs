@@ -1,56 +1,34 @@
 
+use crate::solution::{Answer, ParsedSolution};
 use std::collections::HashMap;
 
-pub fn main() {
-    let mut memory : HashMap<(u64, u64), u64> = HashMap::new();
-
-    println!("Running example tasks for 6 blinks:");
-    println!("125 after 6 blinks is {} stones.", count_stones(125, 6, &mut memory)); // == 7
-    println!("17 after 6 blinks is {} stones.", count_stones(17, 6, &mut memory)); // == 15
+pub struct Day11;
 
-    // Actual task: 28591 78 0 3159881 4254 524155 598 1
-    // Actual blinks: 25
+impl ParsedSolution for Day11 {
+    /// The stone labels, all on one line.
+    type Input = Vec<Vec<u64>>;
 
-    println!("Running actual task for 25 blinks:");
-    println!("28591 after 25 blinks is {} stones.", count_stones(28591, 25, &mut memory));
-    println!("78 after 25 blinks is {} stones.", count_stones(78, 25, &mut memory));
-    println!("0 after 25 blinks is {} stones.", count_stones(0, 25, &mut memory));
-    println!("3159881 after 25 blinks is {} stones.", count_stones(3159881, 25, &mut memory));
-    println!("4254 after 25 blinks is {} stones.", count_stones(4254, 25, &mut memory));
-    println!("524155 after 25 blinks is {} stones.", count_stones(524155, 25, &mut memory));
-    println!("598 after 25 blinks is {} stones.", count_stones(598, 25, &mut memory));
-    println!("1 after 25 blinks is {} stones.", count_stones(1, 25, &mut memory));
+    fn part1(&self, labels: &Self::Input) -> Answer {
+        count_all_stones(labels.iter().flatten(), 25).into()
+    }
 
-    // Sum everything up
-    let mut total = 0;
-    total += count_stones(28591, 25, &mut memory);
-    total += count_stones(78, 25, &mut memory);
-    total += count_stones(0, 25, &mut memory);
-    total += count_stones(3159881, 25, &mut memory);
-    total += count_stones(4254, 25, &mut memory);
-    total += count_stones(524155, 25, &mut memory);
-    total += count_stones(598, 25, &mut memory);
-    total += count_stones(1, 25, &mut memory);
-    println!("Total stones: {}", total);
-    println!("Memory used: {}", memory.len());
+    fn part2(&self, labels: &Self::Input) -> Option<Answer> {
+        // Part two, blink 75 times.
+        Some(count_all_stones(labels.iter().flatten(), 75).into())
+    }
+}
 
-    // Part two, blink 75 times.
+/// Sum up the stones for all labels after the given number of blinks.
+fn count_all_stones<'a>(labels: impl IntoIterator<Item = &'a u64>, blinks: u64) -> u64 {
+    let mut memory : HashMap<(u64, u64), u64> = HashMap::new();
 
     let mut total = 0;
-    total += count_stones(28591, 75, &mut memory);
-    total += count_stones(78, 75, &mut memory);
-    total += count_stones(0, 75, &mut memory);
-    total += count_stones(3159881, 75, &mut memory);
-    total += count_stones(4254, 75, &mut memory);
-    total += count_stones(524155, 75, &mut memory);
-    total += count_stones(598, 75, &mut memory);
-    total += count_stones(1, 75, &mut memory);
-    println!("Total stones: {}", total);
-    println!("Memory used: {}", memory.len());
-
+    for &label in labels {
+        total += count_stones(label, blinks, &mut memory);
+    }
+    total
 }
 
-
 fn count_stones( label : u64, blinks: u64, memory: &mut HashMap<(u64, u64), u64>) -> u64 {
     if blinks == 0 {
         return 1;
@@ -73,19 +51,27 @@ fn count_stones( label : u64, blinks: u64, memory: &mut HashMap<(u64, u64), u64>
 fn split_label( label: u64 ) -> Option<(u64, u64)> {
     // Find out if the label has an even number of decimal digits
     let label_str = label.to_string();
-    if label_str.len() % 2 != 0 {
+    if !label_str.len().is_multiple_of(2) {
         return None;
     }
     let half_len = label_str.len() / 2;
     let first_half = label_str[..half_len].parse().unwrap();
     let second_half = label_str[half_len..].parse().unwrap();
-    return Some((first_half, second_half));
+    Some((first_half, second_half))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_count_stones() {
+        let mut memory = HashMap::new();
+        assert_eq!(count_stones(125, 6, &mut memory), 7);
+        assert_eq!(count_stones(17, 6, &mut memory), 15);
+        assert_eq!(count_all_stones(&[125, 17], 25), 55312);
+    }
+
     #[test]
     fn test_split_label() {
         assert_eq!(split_label(125), None);
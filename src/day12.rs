@@ -1,18 +1,29 @@
-use crate::utils::read_file;
+use crate::solution::{Answer, ParsedSolution};
 use crate::utils::square_grid::SquareCharacterGrid;
 use std::collections::HashMap;
 
-pub fn main() {
-    run_day12_part1("day12-test-a");
-    run_day12_part1("day12-test-b");
-    run_day12_part1("day12");
-}
+pub struct Day12;
+
+impl ParsedSolution for Day12 {
+    type Input = SquareCharacterGrid<i16>;
+
+    fn version(&self) -> Option<u32> {
+        Some(1)
+    }
 
-fn run_day12_part1(filename: &str) {
-    println!("Running day 12 part 1 with file {}", filename);
-    let file = read_file(filename);
-    let grid: SquareCharacterGrid<i16> = SquareCharacterGrid::new(&file);
+    fn part1(&self, grid: &Self::Input) -> Answer {
+        let (total_price, _) = fence_prices(grid);
+        total_price.into()
+    }
+
+    fn part2(&self, grid: &Self::Input) -> Option<Answer> {
+        let (_, reduced_price) = fence_prices(grid);
+        Some(reduced_price.into())
+    }
+}
 
+/// Returns the total price and the reduced (bulk discount) price of all fences.
+fn fence_prices(grid: &SquareCharacterGrid<i16>) -> (usize, usize) {
     /*grid.print();*/
 
     let mut next_garden_index = 0;
@@ -101,26 +112,13 @@ fn run_day12_part1(filename: &str) {
         grid.size(),
     );
 
-    // Print all gardens by size and area (and index)
     for (index, garden) in gardens.iter() {
-        println!(
-            "Garden {} has area {} and circumference {}",
-            index, garden.area, garden.circumference
-        );
         total_price += garden.area * garden.circumference;
         let edge_count =
             edge_count_horizontal.get(index).unwrap() + edge_count_vertical.get(index).unwrap();
-        println!(
-            "Unbroken edges: {} + {} = {}",
-            edge_count_horizontal.get(index).unwrap(),
-            edge_count_vertical.get(index).unwrap(),
-            edge_count
-        );
         reduced_price += garden.area * (edge_count);
     }
-    println!("Total price: {}", total_price);
-    println!("Reduced price: {}", reduced_price);
-    println!();
+    (total_price, reduced_price)
 }
 
 // Finds the garden at a given coordinate
@@ -143,10 +141,7 @@ fn try_find_merged_garden_index(
     y: i16,
 ) -> Option<usize> {
     let garden_index = original_garden_at_coordinate.get(&(x, y));
-    match garden_index {
-        Some(garden_index) => Some(find_garden_index(merges, *garden_index)),
-        None => None,
-    }
+    garden_index.map(|garden_index| find_garden_index(merges, *garden_index))
 }
 
 // Finds the root garden for a given garden index, compressing the chain along the way.
@@ -175,7 +170,7 @@ fn count_horizontal_unbroken_edges(
     (w, h): (i16, i16),
 ) -> HashMap<usize, usize> {
     let mut result = HashMap::new();
-    for (garden_index, _) in gardens {
+    for garden_index in gardens.keys() {
         let mut total = 0;
         for scan_y in -1..h {
             total += count_horizontal_unbroken_edges_in_row(
@@ -198,7 +193,7 @@ fn count_vertical_unbroken_edges(
     (w, h): (i16, i16),
 ) -> HashMap<usize, usize> {
     let mut result = HashMap::new();
-    for (garden_index, _) in gardens {
+    for garden_index in gardens.keys() {
         let mut total = 0;
         for scan_x in -1..w {
             total += count_vertical_unbroken_edges_in_row(
@@ -217,14 +212,18 @@ fn count_vertical_unbroken_edges(
 /// Counts all the edges that are between row scan_y and scan_y + 1
 /// Works by reading a row and representing it as
 ///
-///     00000111100100000
-///     00001111001100111
+/// ```text
+/// 00000111100100000
+/// 00001111001100111
+/// ```
 ///
 /// This finds four unbroken edges:
 ///
-///     00000111100100000
-///         -   - -   ---
-///     00001111001100111
+/// ```text
+/// 00000111100100000
+///     -   - -   ---
+/// 00001111001100111
+/// ```
 fn count_horizontal_unbroken_edges_in_row(
     original_garden_at_coordinate: &HashMap<(i16, i16), usize>,
     merges: &mut HashMap<usize, usize>,
@@ -294,3 +293,20 @@ fn count_vertical_unbroken_edges_in_row(
 
     unbroken_edges
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{crate_root, read_path};
+
+    fn example(name: &str) -> SquareCharacterGrid<i16> {
+        let input = read_path(&crate_root().join("input").join(name), Some(12)).unwrap();
+        SquareCharacterGrid::new(&input)
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(fence_prices(&example("day12-test-a")), (140, 80));
+        assert_eq!(fence_prices(&example("day12-test-b")), (1930, 1206));
+    }
+}
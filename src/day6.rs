@@ -1,51 +1,40 @@
 //! Wandering Guard Problem
 
-use crate::utils::read_file;
+use crate::solution::{Answer, ParsedSolution};
 use crate::utils::square_grid::SquareCharacterGrid;
 use std::collections::HashSet;
 
+pub struct Day6;
 
-pub fn main() {
-    // Read information from day 6 file.
-    let file = read_file("day6");
-    let grid = SquareCharacterGrid::new(&file);
+impl ParsedSolution for Day6 {
+    type Input = SquareCharacterGrid<i32>;
 
-    let mut walls: HashSet<(i32, i32)> = HashSet::new();
+    fn version(&self) -> Option<u32> {
+        Some(1)
+    }
 
-    let mut guard_x: i32 = 0;
-    let mut guard_y: i32 = 0;
+    fn part1(&self, grid: &Self::Input) -> Answer {
+        part1(grid).into()
+    }
 
-    for (x, y, c) in grid.iter() {
-        if c == '#' {
-            walls.insert((x, y));
-        } else if c == '^' {
-            guard_x = x;
-            guard_y = y;
-        }
+    fn part2(&self, grid: &Self::Input) -> Option<Answer> {
+        Some(part2(grid).into())
     }
+}
 
-    let (w, h) = grid.size();
+fn part1(grid: &SquareCharacterGrid<i32>) -> usize {
+    let lab = Lab::from_grid(grid);
 
     // Print the area.
-    // print_area(&walls, w, h, guard_x, guard_y);
-
-    let init_direction = Direction { dx: 0, dy: -1 };
-
-    let (steps_taken, seen_positions, seen_statespace) =
-        walk_throuh_lab(guard_x, guard_y, init_direction, &walls, w, h)
-            .expect("Guard must walk off the board");
-
-    println!("Size of the area: {}x{}", w, h);
-    println!("Guard at: ({}, {})", guard_x, guard_y);
-    println!("Steps taken: {}", steps_taken);
-    println!("Seen positions: {}", seen_positions.len());
-    println!("Seen statespace: {}", seen_statespace.len());
+    // print_area(&lab.walls, lab.w, lab.h, lab.guard_x, lab.guard_y);
 
-    // assert_eq!(seen_positions.len(), 5199);
+    let (_, seen_positions, _) = lab.walk().expect("Guard must walk off the board");
+    seen_positions.len()
+}
 
-    // Part 2
-    //walls.insert((3, 6));
-    //walk_throuh_lab(guard_x, guard_y, init_direction, &walls, w, h);
+fn part2(grid: &SquareCharacterGrid<i32>) -> usize {
+    let lab = Lab::from_grid(grid);
+    let (_, seen_positions, _) = lab.walk().expect("Guard must walk off the board");
 
     let mut positions_with_a_loop = 0;
     /*for x in 0..w {
@@ -58,17 +47,77 @@ pub fn main() {
             }
         }
     }*/
+    // Only positions the guard actually visits can change her path.
     for (x, y) in seen_positions.iter() {
-        let mut new_walls = walls.clone();
+        let mut new_walls = lab.walls.clone();
         new_walls.insert((*x, *y));
-        let walk = walk_throuh_lab(guard_x, guard_y, init_direction, &new_walls, w, h);
+        let walk = walk_throuh_lab(
+            lab.guard_x,
+            lab.guard_y,
+            INIT_DIRECTION,
+            &new_walls,
+            lab.w,
+            lab.h,
+        );
         if walk.is_none() {
             positions_with_a_loop += 1;
         }
     }
-    println!("Positions with a loop: {}", positions_with_a_loop);
+    positions_with_a_loop
+}
+
+const INIT_DIRECTION: Direction = Direction { dx: 0, dy: -1 };
+
+/// The parsed lab: walls, the starting position of the guard and the size of the area.
+struct Lab {
+    walls: HashSet<(i32, i32)>,
+    guard_x: i32,
+    guard_y: i32,
+    w: i32,
+    h: i32,
+}
+
+impl Lab {
+    fn from_grid(grid: &SquareCharacterGrid<i32>) -> Self {
+        let mut walls: HashSet<(i32, i32)> = HashSet::new();
+
+        let mut guard_x: i32 = 0;
+        let mut guard_y: i32 = 0;
+
+        for (x, y, c) in grid.iter() {
+            if c == '#' {
+                walls.insert((x, y));
+            } else if c == '^' {
+                guard_x = x;
+                guard_y = y;
+            }
+        }
+
+        let (w, h) = grid.size();
+        Lab {
+            walls,
+            guard_x,
+            guard_y,
+            w,
+            h,
+        }
+    }
+
+    /// Walk the guard from her starting position, see [walk_throuh_lab].
+    fn walk(&self) -> Option<Walk> {
+        walk_throuh_lab(
+            self.guard_x,
+            self.guard_y,
+            INIT_DIRECTION,
+            &self.walls,
+            self.w,
+            self.h,
+        )
+    }
 }
 
+/// Steps taken, seen positions and seen (position, direction) states of a finished walk.
+type Walk = (i32, HashSet<(i32, i32)>, HashSet<(i32, i32, i32, i32)>);
 
 fn walk_throuh_lab(
     init_guard_x: i32,
@@ -77,7 +126,7 @@ fn walk_throuh_lab(
     walls: &HashSet<(i32, i32)>,
     w: i32,
     h: i32,
-) -> Option<(i32, HashSet<(i32, i32)>, HashSet<(i32, i32, i32, i32)>)> {
+) -> Option<Walk> {
     let mut guard_x = init_guard_x;
     let mut guard_y = init_guard_y;
     let mut direction = init_direction;
@@ -109,7 +158,6 @@ fn walk_throuh_lab(
     Some((steps_taken, seen_positions, seen_statespace))
 }
 
-
 #[derive(Copy, Clone)]
 struct Direction {
     dx: i32,
@@ -126,10 +174,11 @@ impl Direction {
     }
 }
 
+#[allow(dead_code)]
 fn print_area(area: &HashSet<(i32, i32)>, w: i32, h: i32, guard_x: i32, guard_y: i32) {
     for y in 0..h {
         for x in 0..w {
-            if area.contains(&(x as i32, y as i32)) {
+            if area.contains(&(x, y)) {
                 print!("#");
             } else if x == guard_x && y == guard_y {
                 print!("G");
@@ -140,3 +189,17 @@ fn print_area(area: &HashSet<(i32, i32)>, w: i32, h: i32, guard_x: i32, guard_y:
         println!();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{crate_root, read_path};
+
+    #[test]
+    fn test_example() {
+        let input = read_path(&crate_root().join("input/day6-test"), Some(6)).unwrap();
+        let grid = SquareCharacterGrid::new(&input);
+        assert_eq!(part1(&grid), 41);
+        assert_eq!(part2(&grid), 6);
+    }
+}
@@ -0,0 +1,119 @@
+//! The error type of the project.
+
+use crate::utils::parse::ParseError;
+use crate::utils::STDIN_PATH;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum Error {
+    /// An input file does not exist.
+    MissingInput { path: PathBuf, day: Option<u8> },
+    /// An input file exists but cannot be read.
+    UnreadableInput {
+        path: PathBuf,
+        day: Option<u8>,
+        source: io::Error,
+    },
+    /// An input file is not valid UTF-8. `valid_up_to` is the offset of the first bad byte.
+    InputNotUtf8 {
+        path: PathBuf,
+        day: Option<u8>,
+        valid_up_to: usize,
+    },
+    /// An input has the wrong format.
+    Parse(ParseError),
+    /// Reading or writing a file that is not an input failed. `action` is
+    /// what was done, like `read` or `write`.
+    File {
+        path: PathBuf,
+        action: &'static str,
+        source: io::Error,
+    },
+    /// Downloading from `url` failed.
+    Fetch { url: String, message: String },
+    /// Any other error, described by a message.
+    Message(String),
+}
+
+/// Input paths for messages, `-` is stdin.
+fn describe(path: &Path) -> String {
+    if path == Path::new(STDIN_PATH) {
+        "stdin".to_string()
+    } else {
+        path.display().to_string()
+    }
+}
+
+/// " for day N" if the day is known.
+fn for_day(day: &Option<u8>) -> String {
+    day.map_or(String::new(), |day| format!(" for day {}", day))
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingInput { path, day } => write!(
+                f,
+                "Input file {}{} does not exist",
+                describe(path),
+                for_day(day)
+            ),
+            Error::UnreadableInput { path, day, source } => write!(
+                f,
+                "Cannot read input file {}{}: {}",
+                describe(path),
+                for_day(day),
+                source
+            ),
+            Error::InputNotUtf8 {
+                path,
+                day,
+                valid_up_to,
+            } => write!(
+                f,
+                "Input file {}{} is not valid UTF-8 (invalid byte at offset {})",
+                describe(path),
+                for_day(day),
+                valid_up_to
+            ),
+            Error::Parse(error) => write!(f, "Cannot parse input: {}", error),
+            Error::File {
+                path,
+                action,
+                source,
+            } => write!(f, "Cannot {} {}: {}", action, path.display(), source),
+            Error::Fetch { url, message } => write!(f, "Cannot download {}: {}", url, message),
+            Error::Message(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::UnreadableInput { source, .. } | Error::File { source, .. } => Some(source),
+            Error::Parse(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::Message(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Error::Message(message.to_string())
+    }
+}
@@ -0,0 +1,229 @@
+//! Extracts example inputs and their answers from a saved puzzle page.
+//!
+//! Examples are the `<pre><code>` blocks of the puzzle description. The
+//! answer to an example is the last emphasized code (`<code><em>140</em></code>`)
+//! between that example and the next one. A page saved after solving part 1
+//! has a second `<article>` for part 2, and answers found there are for part 2.
+
+use crate::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Sidecar files with example answers end in this, e.g. `day12-test-a.answer`.
+pub const ANSWER_EXTENSION: &str = "answer";
+
+/// An example from the puzzle text with the answers that follow it.
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub input: String,
+    /// `(part, answer)` pairs.
+    pub answers: Vec<(u8, String)>,
+}
+
+/// Find all examples on a puzzle page.
+pub fn extract_examples(html: &str) -> Vec<Example> {
+    let articles = sections(html, "<article", "</article>");
+    let articles = if articles.is_empty() {
+        vec![html]
+    } else {
+        articles
+    };
+    let mut examples: Vec<Example> = vec![];
+    for (index, article) in articles.iter().enumerate() {
+        let part = index as u8 + 1;
+        let blocks = sections(article, "<pre><code>", "</code></pre>");
+        for block in blocks {
+            // The text after each example, up to the next one, may name its answer.
+            let block_end = offset_in(article, block) + block.len();
+            let after_block = &article[block_end..];
+            let following = match after_block.find("<pre><code>") {
+                Some(end) => &after_block[..end],
+                None => after_block,
+            };
+            let answers = last_emphasized_code(following)
+                .map(|answer| vec![(part, answer)])
+                .unwrap_or_default();
+            examples.push(Example {
+                input: html_text(block),
+                answers,
+            });
+        }
+        // Part 2 often reuses the part 1 examples without repeating them.
+        if part > 1 && !article.contains("<pre><code>") {
+            if let (Some(example), Some(answer)) =
+                (examples.last_mut(), last_emphasized_code(article))
+            {
+                example.answers.push((part, answer));
+            }
+        }
+    }
+    examples
+}
+
+/// Byte offset of `part`, which must be a slice of `text`.
+fn offset_in(text: &str, part: &str) -> usize {
+    part.as_ptr() as usize - text.as_ptr() as usize
+}
+
+/// The contents between each `start` tag and the following `end` tag.
+fn sections<'a>(text: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let mut result = vec![];
+    let mut rest = text;
+    while let Some(begin) = rest.find(start) {
+        let after_start = &rest[begin + start.len()..];
+        // `start` may be an opening tag with attributes, like `<article class="day-desc">`.
+        let content_start = if start.ends_with('>') {
+            0
+        } else {
+            after_start.find('>').map_or(after_start.len(), |i| i + 1)
+        };
+        let content = &after_start[content_start..];
+        let Some(stop) = content.find(end) else {
+            break;
+        };
+        result.push(&content[..stop]);
+        rest = &content[stop + end.len()..];
+    }
+    result
+}
+
+fn last_emphasized_code(html: &str) -> Option<String> {
+    let code_em = sections(html, "<code><em>", "</em></code>");
+    let em_code = sections(html, "<em><code>", "</code></em>");
+    // Take whichever comes last in the text.
+    let last = |found: &[&str]| found.last().map(|s| offset_in(html, s));
+    let answer = match (last(&code_em), last(&em_code)) {
+        (Some(a), Some(b)) if b > a => em_code.last(),
+        (Some(_), _) => code_em.last(),
+        (None, _) => em_code.last(),
+    }?;
+    Some(html_text(answer))
+}
+
+/// Strip tags and decode the entities used on puzzle pages.
+fn html_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The file names for the `index`-th example: `a`, `b`, ..., then `aa`, `ab`, ...
+fn example_suffix(index: usize) -> String {
+    let letter = |i: usize| (b'a' + i as u8) as char;
+    if index < 26 {
+        letter(index).to_string()
+    } else {
+        format!("{}{}", letter(index / 26 - 1), letter(index % 26))
+    }
+}
+
+/// Write the examples of a page to `dir/dayN-test-a`, `dir/dayN-test-b`, ...,
+/// and their answers to `dayN-test-a.answer` and so on, one `<part> <answer>`
+/// per line. Fails without writing anything if one of the files exists.
+/// Returns the written files.
+pub fn write_examples(day: u8, examples: &[Example], dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files = vec![];
+    for (index, example) in examples.iter().enumerate() {
+        let name = format!("day{}-test-{}", day, example_suffix(index));
+        files.push((dir.join(&name), example.input.clone()));
+        if !example.answers.is_empty() {
+            let answers: String = example
+                .answers
+                .iter()
+                .map(|(part, answer)| format!("{} {}\n", part, answer))
+                .collect();
+            let sidecar = dir.join(format!("{}.{}", name, ANSWER_EXTENSION));
+            files.push((sidecar, answers));
+        }
+    }
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+        return Err(Error::Message(format!(
+            "{} already exists, not overwriting it",
+            path.display()
+        )));
+    }
+    for (path, contents) in &files {
+        fs::write(path, contents)
+            .map_err(|e| Error::Message(format!("Cannot write {}: {}", path.display(), e)))?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::TempPath;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 12: Garden Groups ---</h2>
+<p>For example:</p>
+<pre><code>AAAA
+BBCD
+</code></pre>
+<p>It has a total price of <code><em>140</em></code>.</p>
+<p>A larger example:</p>
+<pre><code><em>R</em>RRR&lt;&amp;
+</code></pre>
+<p>Area <code>4</code> and total <em><code>1930</code></em>.</p>
+</article>
+<p>Your puzzle answer was <code>1450816</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>The larger example now has a new price of <code><em>1206</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract_examples() {
+        assert_eq!(
+            extract_examples(PAGE),
+            vec![
+                Example {
+                    input: "AAAA\nBBCD\n".to_string(),
+                    answers: vec![(1, "140".to_string())],
+                },
+                Example {
+                    input: "RRRR<&\n".to_string(),
+                    answers: vec![(1, "1930".to_string()), (2, "1206".to_string())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_examples() {
+        let dir = TempPath::new("extract");
+        fs::create_dir_all(&dir).unwrap();
+        let examples = extract_examples(PAGE);
+
+        let written = write_examples(12, &examples, &dir).unwrap();
+        assert_eq!(written.len(), 4);
+        assert_eq!(
+            fs::read_to_string(dir.join("day12-test-a")).unwrap(),
+            "AAAA\nBBCD\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("day12-test-b.answer")).unwrap(),
+            "1 1930\n2 1206\n"
+        );
+        assert!(write_examples(12, &examples, &dir).is_err());
+    }
+
+    #[test]
+    fn test_example_suffix() {
+        assert_eq!(example_suffix(0), "a");
+        assert_eq!(example_suffix(25), "z");
+        assert_eq!(example_suffix(26), "aa");
+    }
+}
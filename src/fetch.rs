@@ -0,0 +1,271 @@
+//! Downloads puzzle inputs into the input directory.
+//!
+//! HTTPS downloads are done by `curl`, which handles TLS for us, so `curl`
+//! has to be installed and on the `PATH` to fetch from the real site. Plain
+//! `http://` URLs are fetched with a minimal built-in HTTP/1.1 client instead,
+//! so the fetcher can be tested against a local HTTP server without `curl`.
+
+use crate::error::Error;
+use crate::utils::crate_root;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// File in the crate root with the session token, if it is not in the environment.
+pub const SESSION_FILE: &str = ".aoc-session";
+
+/// Sent with every request, so the Advent of Code team knows whom to contact.
+pub const USER_AGENT: &str = concat!(
+    "aoc2024/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/kreibaum/aoc2024 input fetcher)"
+);
+
+const YEAR: u16 = 2024;
+
+/// The result of fetching an input.
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    /// The file already existed, nothing was downloaded.
+    Cached(PathBuf),
+}
+
+/// The base URL from `AOC_BASE_URL`, or the real Advent of Code site.
+pub fn base_url() -> String {
+    match std::env::var(BASE_URL_ENV) {
+        Ok(url) if !url.is_empty() => url,
+        _ => DEFAULT_BASE_URL.to_string(),
+    }
+}
+
+/// The session token from `AOC_SESSION`, or from `.aoc-session` in the crate root.
+pub fn session_token() -> Result<String, Error> {
+    if let Ok(token) = std::env::var(SESSION_ENV) {
+        if !token.trim().is_empty() {
+            return Ok(token.trim().to_string());
+        }
+    }
+    let path = crate_root().join(SESSION_FILE);
+    match fs::read_to_string(&path) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        _ => Err(Error::Message(format!(
+            "No session token. Set {} or put it into {}",
+            SESSION_ENV,
+            path.display()
+        ))),
+    }
+}
+
+/// Download the input of a day into `dir/dayN`, unless that file already exists.
+/// The session token is only looked up if something needs to be downloaded.
+pub fn fetch_input<S: FnOnce() -> Result<String, Error>>(
+    day: u8,
+    dir: &Path,
+    base_url: &str,
+    session: S,
+) -> Result<Fetched, Error> {
+    let path = dir.join(format!("day{}", day));
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+    let url = format!(
+        "{}/{}/day/{}/input",
+        base_url.trim_end_matches('/'),
+        YEAR,
+        day
+    );
+    let body = download(&url, &session()?)?;
+    // An empty input would count as cached and never be downloaded again.
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Err(Error::Fetch {
+            url,
+            message: "the response is empty".to_string(),
+        });
+    }
+
+    // Write to a temporary file first, so an interrupted write never leaves
+    // a truncated input behind that would then count as cached.
+    let write_error =
+        |e: std::io::Error| Error::Message(format!("Cannot write {}: {}", path.display(), e));
+    fs::create_dir_all(dir).map_err(write_error)?;
+    let partial = dir.join(format!(".day{}.part", day));
+    fs::write(&partial, body).map_err(write_error)?;
+    fs::rename(&partial, &path).map_err(write_error)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+fn download(url: &str, session: &str) -> Result<Vec<u8>, Error> {
+    match url.strip_prefix("http://") {
+        Some(location) => download_plain(url, location, session),
+        None => download_with_curl(url, session),
+    }
+}
+
+/// A GET request over a plain TCP connection, for `http://` URLs.
+/// `location` is the URL without the scheme. Redirects are not followed.
+fn download_plain(url: &str, location: &str, session: &str) -> Result<Vec<u8>, Error> {
+    let fetch_error = |message: String| Error::Fetch {
+        url: url.to_string(),
+        message,
+    };
+    let (host, path) = match location.find('/') {
+        Some(slash) => location.split_at(slash),
+        None => (location, "/"),
+    };
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+    let mut stream = TcpStream::connect(&address)
+        .map_err(|e| fetch_error(format!("cannot connect to {}: {}", address, e)))?;
+    write!(
+        stream,
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: session={}\r\nConnection: close\r\n\r\n",
+        path, host, USER_AGENT, session
+    )
+    .map_err(|e| fetch_error(format!("cannot send the request: {}", e)))?;
+    let mut response = vec![];
+    stream
+        .read_to_end(&mut response)
+        .map_err(|e| fetch_error(format!("cannot read the response: {}", e)))?;
+
+    let head_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(|| fetch_error("incomplete response".to_string()))?;
+    let head = String::from_utf8_lossy(&response[..head_end]);
+    let status = head.lines().next().unwrap_or_default();
+    let status = status.split_once(' ').map_or(status, |(_, status)| status);
+    if !status.starts_with('2') {
+        return Err(fetch_error(format!("the server returned {}", status)));
+    }
+    let chunked = head.lines().any(|line| {
+        line.to_ascii_lowercase()
+            .starts_with("transfer-encoding: chunked")
+    });
+    if chunked {
+        return Err(fetch_error(
+            "chunked responses are not supported".to_string(),
+        ));
+    }
+    Ok(response.split_off(head_end + 4))
+}
+
+fn download_with_curl(url: &str, session: &str) -> Result<Vec<u8>, Error> {
+    let fetch_error = |message: String| Error::Fetch {
+        url: url.to_string(),
+        message,
+    };
+    // The cookie header goes through stdin, so the token does not show up in the process list.
+    let mut curl = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--user-agent", USER_AGENT, "--header", "@-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| fetch_error(format!("cannot run curl, is it installed? ({})", e)))?;
+    let mut stdin = curl.stdin.take().expect("stdin is piped");
+    writeln!(stdin, "Cookie: session={}", session)
+        .map_err(|e| fetch_error(format!("cannot pass headers to curl: {}", e)))?;
+    drop(stdin);
+    let output = curl
+        .wait_with_output()
+        .map_err(|e| fetch_error(format!("curl failed: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(fetch_error(stderr.trim().to_string()));
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::TempPath;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serve a single request with the given status line and body, and return its head.
+    fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut head = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+            head
+        });
+        (base_url, server)
+    }
+
+    #[test]
+    fn test_fetch_and_cache() {
+        let dir = TempPath::new("fetch");
+        let (base_url, server) = serve_once("200 OK", "125 17\n");
+        let session = || Ok("s3cr3t".to_string());
+
+        let fetched = fetch_input(11, &dir, &base_url, session).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded(dir.join("day11")));
+        assert_eq!(fs::read_to_string(dir.join("day11")).unwrap(), "125 17\n");
+
+        let head = server.join().unwrap();
+        assert!(head.starts_with("GET /2024/day/11/input HTTP/1.1\r\n"));
+        assert!(head.contains(&format!("User-Agent: {}\r\n", USER_AGENT)));
+        assert!(head.contains("Cookie: session=s3cr3t\r\n"));
+
+        // The server is gone, so this only works because nothing is downloaded.
+        let no_session = || -> Result<String, Error> { panic!("session must not be needed") };
+        let fetched = fetch_input(11, &dir, &base_url, no_session).unwrap();
+        assert_eq!(fetched, Fetched::Cached(dir.join("day11")));
+    }
+
+    #[test]
+    fn test_fetch_error() {
+        let dir = TempPath::new("fetch-error");
+        let (base_url, server) = serve_once("404 Not Found", "Not found");
+        let error = fetch_input(12, &dir, &base_url, || Ok("s3cr3t".to_string())).unwrap_err();
+        server.join().unwrap();
+        assert!(error.to_string().contains("/2024/day/12/input"));
+        assert!(error.to_string().contains("404"));
+        assert!(!dir.join("day12").exists());
+    }
+
+    #[test]
+    fn test_fetch_empty_body() {
+        let dir = TempPath::new("fetch-empty");
+        let (base_url, server) = serve_once("200 OK", "\n");
+        let error = fetch_input(13, &dir, &base_url, || Ok("s3cr3t".to_string())).unwrap_err();
+        server.join().unwrap();
+        assert!(matches!(error, Error::Fetch { .. }));
+        assert!(error.to_string().contains("the response is empty"));
+        assert!(!dir.join("day13").exists());
+    }
+}
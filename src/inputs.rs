@@ -0,0 +1,191 @@
+//! Finds the input files of a day by naming convention.
+//!
+//! The real input of day `N` is `input/dayN`. Examples from the puzzle text
+//! are `input/dayN-test`, or `input/dayN-test-a`, `input/dayN-test-b`, ...
+//! if a puzzle has more than one example. See [input_dir] for where the
+//! `input` directory is.
+
+use crate::error::Error;
+use crate::utils::{input_dir, read_path, STDIN_PATH};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Which input files to run a day on.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum InputSelection {
+    /// The real puzzle input, `dayN`.
+    #[default]
+    Real,
+    /// All examples, or only the one with the given suffix (`b` for `dayN-test-b`).
+    Examples(Option<String>),
+    /// An explicit file path, ignoring the naming convention. `-` is stdin.
+    Path(PathBuf),
+}
+
+/// What an input file is, according to its name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputKind {
+    Real,
+    /// An example, with its suffix if it has one.
+    Example(Option<String>),
+    /// An explicitly selected file.
+    Other,
+}
+
+/// An input file for a day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputFile {
+    pub day: u8,
+    pub name: String,
+    pub kind: InputKind,
+    pub path: PathBuf,
+}
+
+impl InputFile {
+    pub fn read(&self) -> Result<String, Error> {
+        read_path(&self.path, Some(self.day))
+    }
+}
+
+/// Classify a file name as an input of the given day. Returns `None` if the
+/// file does not belong to that day.
+pub fn classify(day: u8, file_name: &str) -> Option<InputKind> {
+    let rest = file_name.strip_prefix(&format!("day{}", day))?;
+    if rest.is_empty() {
+        return Some(InputKind::Real);
+    }
+    let rest = rest.strip_prefix("-test")?;
+    if rest.is_empty() {
+        return Some(InputKind::Example(None));
+    }
+    let suffix = rest.strip_prefix('-')?;
+    // Names with an extension, like `day12-test-a.answer`, are not inputs.
+    if suffix.is_empty() || suffix.contains('.') {
+        return None;
+    }
+    Some(InputKind::Example(Some(suffix.to_string())))
+}
+
+/// All input files of a day, the real input first and then the examples by name.
+pub fn discover(day: u8) -> Vec<InputFile> {
+    discover_in(&input_dir(), day)
+}
+
+/// Like [discover], but looks in `dir` instead of the input directory.
+pub fn discover_in(dir: &Path, day: u8) -> Vec<InputFile> {
+    let mut files: Vec<InputFile> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_file())
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let kind = classify(day, &name)?;
+                    Some(InputFile {
+                        day,
+                        name,
+                        kind,
+                        path: entry.path(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by_key(|file| (file.kind != InputKind::Real, file.name.clone()));
+    files
+}
+
+/// Find the input files for a selection. Fails if nothing matches.
+pub fn select(day: u8, selection: &InputSelection) -> Result<Vec<InputFile>, Error> {
+    select_in(&input_dir(), day, selection)
+}
+
+fn select_in(dir: &Path, day: u8, selection: &InputSelection) -> Result<Vec<InputFile>, Error> {
+    if let InputSelection::Path(path) = selection {
+        let name = if path == Path::new(STDIN_PATH) {
+            "stdin".to_string()
+        } else {
+            path.display().to_string()
+        };
+        return Ok(vec![InputFile {
+            day,
+            name,
+            kind: InputKind::Other,
+            path: path.clone(),
+        }]);
+    }
+    let selected: Vec<InputFile> = discover_in(dir, day)
+        .into_iter()
+        .filter(|file| match (selection, &file.kind) {
+            (InputSelection::Real, InputKind::Real) => true,
+            (InputSelection::Examples(None), InputKind::Example(_)) => true,
+            (InputSelection::Examples(Some(wanted)), InputKind::Example(Some(suffix))) => {
+                wanted == suffix
+            }
+            _ => false,
+        })
+        .collect();
+    if selected.is_empty() {
+        let dir = dir.display();
+        let message = match selection {
+            InputSelection::Examples(Some(suffix)) => format!(
+                "No example '{}' for day {} (expected {}/day{}-test-{})",
+                suffix, day, dir, day, suffix
+            ),
+            InputSelection::Examples(None) => format!(
+                "No examples for day {} (expected {}/day{}-test)",
+                day, dir, day
+            ),
+            _ => format!(
+                "No real input for day {} (expected {}/day{})",
+                day, dir, day
+            ),
+        };
+        return Err(Error::Message(message));
+    }
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::crate_root;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify(12, "day12"), Some(InputKind::Real));
+        assert_eq!(classify(6, "day6-test"), Some(InputKind::Example(None)));
+        assert_eq!(
+            classify(12, "day12-test-b"),
+            Some(InputKind::Example(Some("b".to_string())))
+        );
+        assert_eq!(classify(1, "day12"), None);
+        assert_eq!(classify(1, "day11-test"), None);
+        assert_eq!(classify(12, "day12-test-"), None);
+        assert_eq!(classify(12, "day12.txt"), None);
+        assert_eq!(classify(12, "day12-test-a.answer"), None);
+    }
+
+    #[test]
+    fn test_select() {
+        // The inputs in the repository, wherever AOC_INPUT_DIR points.
+        let dir = crate_root().join("input");
+        let names = |selection| -> Vec<String> {
+            select_in(&dir, 12, &selection)
+                .unwrap()
+                .into_iter()
+                .map(|file| file.name)
+                .collect()
+        };
+        assert_eq!(names(InputSelection::Real), vec!["day12"]);
+        assert_eq!(
+            names(InputSelection::Examples(None)),
+            vec!["day12-test-a", "day12-test-b"]
+        );
+        assert_eq!(
+            names(InputSelection::Examples(Some("b".to_string()))),
+            vec!["day12-test-b"]
+        );
+        assert!(select_in(&dir, 12, &InputSelection::Examples(Some("z".to_string()))).is_err());
+    }
+}
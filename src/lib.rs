@@ -0,0 +1,22 @@
+//! Advent of Code 2024 solutions and the utilities they share.
+//!
+//! The `aoc2024` binary is a thin command line interface on top of this library.
+
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod day11;
+pub mod day12;
+pub mod day6;
+pub mod error;
+pub mod extract;
+pub mod fetch;
+pub mod inputs;
+pub mod output;
+pub mod puzzle_input;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod utils;
+pub mod watch;
@@ -1,10 +1,344 @@
-mod day11;
-mod utils;
-mod day6;
-mod day12;
-
-fn main() {
-    // day11::main();
-    // day6::main()
-    day12::main();
+mod cli;
+
+use aoc2024::answers::{self, Answers, Outcome, Verdict};
+use aoc2024::cache::AnswerCache;
+use aoc2024::error::Error;
+use aoc2024::fetch::{self, Fetched};
+use aoc2024::inputs;
+use aoc2024::output::{self, Format};
+use aoc2024::solution::Timing;
+use aoc2024::{bench, extract, registry, runner, scaffold, utils, watch};
+use cli::Command;
+use std::hint::black_box;
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
+use std::time::Instant;
+
+fn main() -> ExitCode {
+    let cli = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(message) => {
+            eprintln!("Error: {}\n\n{}", message, cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    if let Some(input_dir) = cli.input_dir {
+        utils::set_input_dir(input_dir);
+    }
+    match run(cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), Error> {
+    match command {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::Run {
+            day,
+            part,
+            inputs,
+            format,
+            cache,
+        } => {
+            let day = registry::find_day(day).ok_or_else(|| unknown_day(day))?;
+            let cache = cache.then(AnswerCache::open);
+            // Part 2 may not be solved yet, that is only an error if it was asked for.
+            let explicit_part = part.is_some();
+            let mut results = vec![];
+            'inputs: for input_file in inputs::select(day.number, &inputs)? {
+                let input = input_file.read()?;
+                for part in selected_parts(part) {
+                    let job = runner::Job {
+                        day,
+                        part,
+                        input_name: &input_file.name,
+                        input: &input,
+                        cache: cache.as_ref(),
+                    };
+                    let result = job.run();
+                    match &result.answer {
+                        Ok(Some(answer)) => {
+                            if format == Format::Text {
+                                let cached = if result.cached { " (cached)" } else { "" };
+                                println!(
+                                    "Day {} part {} ({}): {}{}",
+                                    day.number, part, input_file.name, answer, cached
+                                );
+                            }
+                        }
+                        Ok(None) if explicit_part => return Err(no_such_part(day.number, part)),
+                        Ok(None) => {
+                            if format == Format::Text {
+                                println!(
+                                    "Day {} part {} ({}): (not solved)",
+                                    day.number, part, input_file.name
+                                );
+                            }
+                        }
+                        Err(_) => {
+                            results.push(result);
+                            break 'inputs;
+                        }
+                    }
+                    results.push(result);
+                }
+            }
+            match format {
+                Format::Text => {}
+                Format::Json => println!("{}", output::to_json(&results)),
+                Format::Csv => print!("{}", output::to_csv(&results)),
+            }
+            // A failed part ends the run, after the results so far are printed.
+            if let Some(error) = results.into_iter().find_map(|result| result.answer.err()) {
+                return Err(error);
+            }
+        }
+        Command::RunAll {
+            part,
+            threads,
+            format,
+            cache,
+        } => run_all(part, threads, format, cache)?,
+        Command::Verify => verify()?,
+        Command::Bench {
+            day,
+            part,
+            inputs,
+            runs,
+            warmup,
+        } => {
+            let day = registry::find_day(day).ok_or_else(|| unknown_day(day))?;
+            let explicit_part = part.is_some();
+            for input_file in inputs::select(day.number, &inputs)? {
+                let input = input_file.read()?;
+                for part in selected_parts(part) {
+                    if day.solution.solve(part, &input)?.is_none() {
+                        if explicit_part {
+                            return Err(no_such_part(day.number, part));
+                        }
+                        println!(
+                            "Day {} part {} ({}): (not solved)",
+                            day.number, part, input_file.name
+                        );
+                        continue;
+                    }
+                    let timings = bench::measure_parts(warmup, runs, || {
+                        let (answer, timing) = day.solution.solve_timed(part, black_box(&input))?;
+                        black_box(answer);
+                        Ok::<_, Error>(timing)
+                    })?
+                    .expect("at least one run");
+                    let stats = |timings: &bench::Timings| {
+                        format!(
+                            "min {:?}, median {:?}, max {:?}",
+                            timings.min, timings.median, timings.max
+                        )
+                    };
+                    let parse = timings.parse.as_ref().map_or(String::new(), |parse| {
+                        format!("parse {}; solve ", stats(parse))
+                    });
+                    println!(
+                        "Day {} part {} ({}): {}{} over {} runs",
+                        day.number,
+                        part,
+                        input_file.name,
+                        parse,
+                        stats(&timings.solve),
+                        timings.solve.runs
+                    );
+                }
+            }
+        }
+        Command::Watch {
+            day,
+            part,
+            inputs,
+            interval,
+        } => {
+            let day = registry::find_day(day).ok_or_else(|| unknown_day(day))?;
+            watch::watch(day.number, &inputs, interval, |input_files| {
+                println!("--- Running day {}", day.number);
+                for input_file in input_files {
+                    // The file may be gone again by now.
+                    let input = match input_file.read() {
+                        Ok(input) => input,
+                        Err(error) => {
+                            eprintln!("Error: {}", error);
+                            continue;
+                        }
+                    };
+                    for part in selected_parts(part) {
+                        // A half-edited example may crash the solution, that must not end the watch.
+                        let start = Instant::now();
+                        let answer = panic::catch_unwind(AssertUnwindSafe(|| {
+                            day.solution.solve_timed(part, &input)
+                        }));
+                        let failed = |message: String| {
+                            let timing = Timing {
+                                parse: None,
+                                solve: start.elapsed(),
+                            };
+                            (message, timing)
+                        };
+                        let (answer, timing) = match answer {
+                            Ok(Ok((Some(answer), timing))) => (answer.to_string(), timing),
+                            Ok(Ok((None, timing))) => ("(not solved)".to_string(), timing),
+                            Ok(Err(error)) => failed(format!("(failed: {})", error)),
+                            Err(_) => failed("(panicked)".to_string()),
+                        };
+                        let parse = timing
+                            .parse
+                            .map_or(String::new(), |parse| format!(", parsed in {:.2?}", parse));
+                        println!(
+                            "Day {} part {} ({}): {} in {:.2?}{}",
+                            day.number, part, input_file.name, answer, timing.solve, parse
+                        );
+                    }
+                }
+            });
+        }
+        Command::New { day } => {
+            for path in scaffold::create_day(day)? {
+                println!("Created {}", path.display());
+            }
+            println!("Registered day {} in src/lib.rs and src/registry.rs", day);
+        }
+        Command::Fetch { day, base_url } => {
+            let base_url = base_url.unwrap_or_else(fetch::base_url);
+            let dir = utils::input_dir();
+            match fetch::fetch_input(day, &dir, &base_url, fetch::session_token)? {
+                Fetched::Downloaded(path) => println!("Downloaded {}", path.display()),
+                Fetched::Cached(path) => {
+                    println!(
+                        "{} already exists, not downloading it again",
+                        path.display()
+                    )
+                }
+            }
+        }
+        Command::Extract { day, page } => {
+            let html = utils::read_path(&page, Some(day))?;
+            let examples = extract::extract_examples(&html);
+            if examples.is_empty() {
+                return Err(Error::Message(format!(
+                    "No examples found in {}",
+                    page.display()
+                )));
+            }
+            for path in extract::write_examples(day, &examples, &utils::input_dir())? {
+                println!("Created {}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The given part, or both parts if none is given.
+fn selected_parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+/// Run every day on its real input in parallel and print a summary table.
+fn run_all(
+    part: Option<u8>,
+    threads: Option<usize>,
+    format: Format,
+    cache: bool,
+) -> Result<(), Error> {
+    let cache = cache.then(AnswerCache::open);
+    let start = Instant::now();
+    let results = runner::run_all(
+        registry::DAYS,
+        &utils::input_dir(),
+        &selected_parts(part),
+        threads.unwrap_or_else(runner::default_threads),
+        cache.as_ref(),
+    );
+    match format {
+        Format::Text => {
+            output::print_table(&results);
+            println!("\nTotal wall time: {:.2?}", start.elapsed());
+        }
+        Format::Json => println!("{}", output::to_json(&results)),
+        Format::Csv => print!("{}", output::to_csv(&results)),
+    }
+
+    let failed: Vec<_> = results.iter().filter(|result| result.failed()).collect();
+    if format == Format::Text {
+        for result in &failed {
+            if let Err(error) = &result.answer {
+                eprintln!(
+                    "Error: day {} part {} ({}): {}",
+                    result.day, result.part, result.input_name, error
+                );
+            }
+        }
+    }
+    if !failed.is_empty() {
+        return Err(Error::Message(format!(
+            "{} of {} parts failed",
+            failed.len(),
+            results.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Run every day on all of its input files and compare against the answers file.
+fn verify() -> Result<(), Error> {
+    let answers = Answers::load()?;
+    let report = answers::verify(registry::DAYS, &utils::input_dir(), &answers)?;
+    for check in &report.checks {
+        let label = format!(
+            "day {:>2} part {} {:<14}",
+            check.day, check.part, check.input_name
+        );
+        match &check.outcome {
+            Outcome::Failed(error) => println!("ERROR    {} {}", label, error),
+            Outcome::Checked { answer, verdict } => match verdict {
+                Verdict::Pass => println!("pass     {} {}", label, answer),
+                Verdict::Fail { expected } => {
+                    println!("FAIL     {} {} (expected {})", label, answer, expected)
+                }
+                Verdict::Missing => println!("missing  {} {}", label, answer),
+            },
+        }
+    }
+    println!(
+        "{} passed, {} failed, {} missing",
+        report.passed(),
+        report.failed(),
+        report.missing()
+    );
+    if report.failed() > 0 {
+        return Err(Error::Message(format!(
+            "{} answers fail or do not match {}",
+            report.failed(),
+            answers::ANSWERS_FILE
+        )));
+    }
+    Ok(())
+}
+
+fn no_such_part(day: u8, part: u8) -> Error {
+    Error::Message(format!("Day {} has no part {}", day, part))
+}
+
+fn unknown_day(day: u8) -> Error {
+    let known: Vec<String> = registry::DAYS
+        .iter()
+        .map(|day| day.number.to_string())
+        .collect();
+    Error::Message(format!(
+        "Day {} is not solved yet. Known days: {}",
+        day,
+        known.join(", ")
+    ))
 }
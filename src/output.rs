@@ -0,0 +1,239 @@
+//! Formats job results as a text table, JSON or CSV.
+
+use crate::error::Error;
+use crate::runner::JobResult;
+use crate::solution::Answer;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Output format for results.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Format {
+    /// Human readable output.
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "Unknown format '{}', expected text, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+/// Print results as a table with one row per job.
+pub fn print_table(results: &[JobResult]) {
+    let header = ["Day", "Part", "Input", "Answer", "Parse", "Solve"];
+    let rows: Vec<[String; 6]> = results
+        .iter()
+        .map(|r| {
+            [
+                r.day.to_string(),
+                r.part.to_string(),
+                r.input_name.clone(),
+                match &r.answer {
+                    Ok(Some(answer)) => answer.to_string(),
+                    Ok(None) => "(not solved)".to_string(),
+                    Err(Error::MissingInput { .. }) => "(missing input)".to_string(),
+                    Err(_) => "(failed)".to_string(),
+                },
+                r.parse_time
+                    .map_or("-".to_string(), |parse| format!("{:.2?}", parse)),
+                match &r.answer {
+                    _ if r.cached => "(cached)".to_string(),
+                    Err(Error::MissingInput { .. }) => "-".to_string(),
+                    _ => format!("{:.2?}", r.elapsed),
+                },
+            ]
+        })
+        .collect();
+
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let print_row = |cells: &[&str]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    };
+    print_row(&header);
+    let separator: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    print_row(&separator.iter().map(String::as_str).collect::<Vec<_>>());
+    for row in &rows {
+        print_row(&row.iter().map(String::as_str).collect::<Vec<_>>());
+    }
+}
+
+/// Milliseconds with microsecond precision.
+fn milliseconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+/// One JSON object per result, in a JSON array. Numeric answers are JSON
+/// numbers, text answers are strings and unsolved or failed parts are `null`.
+/// So is `parse_ms` if the parse time is not known, and `error` unless the
+/// job failed. For cached answers, `runtime_ms` is the time of the cache lookup.
+pub fn to_json(results: &[JobResult]) -> String {
+    let mut json = String::from("[\n");
+    for (index, result) in results.iter().enumerate() {
+        let answer = match &result.answer {
+            Ok(Some(Answer::Number(n))) => n.to_string(),
+            Ok(Some(Answer::Text(text))) => json_string(text),
+            Ok(None) | Err(_) => "null".to_string(),
+        };
+        let error = match &result.answer {
+            Err(error) => json_string(&error.to_string()),
+            Ok(_) => "null".to_string(),
+        };
+        let separator = if index + 1 < results.len() { "," } else { "" };
+        writeln!(
+            json,
+            "  {{\"day\": {}, \"part\": {}, \"input\": {}, \"answer\": {}, \"parse_ms\": {}, \"runtime_ms\": {}, \"cached\": {}, \"error\": {}}}{}",
+            result.day,
+            result.part,
+            json_string(&result.input_name),
+            answer,
+            result.parse_time.map_or("null".to_string(), milliseconds),
+            milliseconds(result.elapsed),
+            result.cached,
+            error,
+            separator
+        )
+        .unwrap();
+    }
+    json.push(']');
+    json
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// A header line and one CSV line per result. Unsolved and failed parts have
+/// an empty answer, unknown parse times and the error of successful jobs are
+/// empty too.
+pub fn to_csv(results: &[JobResult]) -> String {
+    let mut csv = String::from("day,part,input,answer,parse_ms,runtime_ms,cached,error\n");
+    for result in results {
+        let (answer, error) = match &result.answer {
+            Ok(answer) => (
+                answer
+                    .as_ref()
+                    .map_or(String::new(), |answer| answer.to_string()),
+                String::new(),
+            ),
+            Err(error) => (String::new(), error.to_string()),
+        };
+        writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{}",
+            result.day,
+            result.part,
+            csv_field(&result.input_name),
+            csv_field(&answer),
+            result.parse_time.map_or(String::new(), milliseconds),
+            milliseconds(result.elapsed),
+            result.cached,
+            csv_field(&error)
+        )
+        .unwrap();
+    }
+    csv
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<JobResult> {
+        vec![
+            JobResult {
+                day: 6,
+                part: 1,
+                input_name: "day6".to_string(),
+                answer: Ok(Some(Answer::Number(5199))),
+                parse_time: Some(Duration::from_micros(250)),
+                elapsed: Duration::from_micros(1742),
+                cached: false,
+            },
+            JobResult {
+                day: 6,
+                part: 2,
+                input_name: "day6, \"copy\"".to_string(),
+                answer: Ok(None),
+                parse_time: None,
+                elapsed: Duration::from_millis(4060),
+                cached: true,
+            },
+            JobResult {
+                day: 12,
+                part: 1,
+                input_name: "day12".to_string(),
+                answer: Err(Error::Message("bad, \"input\"".to_string())),
+                parse_time: None,
+                elapsed: Duration::from_micros(5),
+                cached: false,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            to_json(&results()),
+            "[\n  {\"day\": 6, \"part\": 1, \"input\": \"day6\", \"answer\": 5199, \"parse_ms\": 0.250, \"runtime_ms\": 1.742, \"cached\": false, \"error\": null},\n  \
+             {\"day\": 6, \"part\": 2, \"input\": \"day6, \\\"copy\\\"\", \"answer\": null, \"parse_ms\": null, \"runtime_ms\": 4060.000, \"cached\": true, \"error\": null},\n  \
+             {\"day\": 12, \"part\": 1, \"input\": \"day12\", \"answer\": null, \"parse_ms\": null, \"runtime_ms\": 0.005, \"cached\": false, \"error\": \"bad, \\\"input\\\"\"}\n]"
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        assert_eq!(
+            to_csv(&results()),
+            "day,part,input,answer,parse_ms,runtime_ms,cached,error\n6,1,day6,5199,0.250,1.742,false,\n6,2,\"day6, \"\"copy\"\"\",,,4060.000,true,\n\
+             12,1,day12,,,0.005,false,\"bad, \"\"input\"\"\"\n"
+        );
+    }
+}
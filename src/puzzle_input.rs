@@ -0,0 +1,132 @@
+//! Typed puzzle inputs, so a solution can declare what its input looks like
+//! instead of parsing the text itself. See [crate::solution::ParsedSolution].
+
+use crate::utils::parse::{self, ParseError, SectionReader};
+use crate::utils::square_grid::{ByteGrid, SquareCharacterGrid};
+use std::fmt;
+use std::str::FromStr;
+
+/// A structure that can be parsed from the full text of a puzzle input.
+pub trait FromPuzzleInput: Sized {
+    fn from_puzzle_input(input: &str) -> Result<Self, ParseError>;
+}
+
+/// The raw text, for days that do their own parsing.
+impl FromPuzzleInput for String {
+    fn from_puzzle_input(input: &str) -> Result<Self, ParseError> {
+        Ok(input.to_string())
+    }
+}
+
+impl<Coord: num::PrimInt> FromPuzzleInput for SquareCharacterGrid<Coord> {
+    fn from_puzzle_input(input: &str) -> Result<Self, ParseError> {
+        SquareCharacterGrid::try_new(input)
+    }
+}
+
+impl<Coord: num::PrimInt> FromPuzzleInput for ByteGrid<Coord> {
+    fn from_puzzle_input(input: &str) -> Result<Self, ParseError> {
+        ByteGrid::try_from_ascii(input)
+    }
+}
+
+/// Rows of whitespace-separated numbers, one row per line.
+impl<T> FromPuzzleInput for Vec<Vec<T>>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn from_puzzle_input(input: &str) -> Result<Self, ParseError> {
+        parse::lines_with(input, parse::numbers)
+    }
+}
+
+/// A left and a right column of numbers.
+impl<T> FromPuzzleInput for (Vec<T>, Vec<T>)
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn from_puzzle_input(input: &str) -> Result<Self, ParseError> {
+        let [left, right] = parse::columns::<2, T>(input)?;
+        Ok((left, right))
+    }
+}
+
+/// Sections separated by blank lines, each parsed as `T`. Use a
+/// [SectionReader] for sections of different types.
+#[derive(Debug, PartialEq)]
+pub struct Sections<T>(pub Vec<T>);
+
+impl<T: FromPuzzleInput> FromPuzzleInput for Sections<T> {
+    fn from_puzzle_input(input: &str) -> Result<Self, ParseError> {
+        let mut reader = SectionReader::new(input);
+        let mut sections = vec![];
+        while reader.remaining() > 0 {
+            // Sections are named by their number.
+            let name = (sections.len() + 1).to_string();
+            sections.push(reader.parse_next(&name, T::from_puzzle_input)?);
+        }
+        Ok(Sections(sections))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shapes() {
+        let rows: Vec<Vec<i64>> = FromPuzzleInput::from_puzzle_input("7 6 4\n1 2\n").unwrap();
+        assert_eq!(rows, vec![vec![7, 6, 4], vec![1, 2]]);
+
+        let columns: (Vec<u32>, Vec<u32>) =
+            FromPuzzleInput::from_puzzle_input("3   4\n4   3\n").unwrap();
+        assert_eq!(columns, (vec![3, 4], vec![4, 3]));
+
+        let grid: SquareCharacterGrid<i32> =
+            FromPuzzleInput::from_puzzle_input("..#\n#..\n").unwrap();
+        assert_eq!(grid.size(), (3, 2));
+    }
+
+    #[test]
+    fn test_sections() {
+        let sections: Sections<Vec<Vec<u8>>> =
+            FromPuzzleInput::from_puzzle_input("1 2\n3\n\n4\n").unwrap();
+        assert_eq!(
+            sections,
+            Sections(vec![vec![vec![1, 2], vec![3]], vec![vec![4]]])
+        );
+
+        let error = Sections::<Vec<Vec<u8>>>::from_puzzle_input("1 2\n3\n\n4\nx\n").unwrap_err();
+        assert_eq!((error.line, error.column), (5, 1));
+        assert_eq!(error.section.as_deref(), Some("2"));
+    }
+
+    /// A warehouse map followed by a list of moves.
+    struct Warehouse {
+        map: SquareCharacterGrid<i32>,
+        moves: Vec<char>,
+    }
+
+    impl FromPuzzleInput for Warehouse {
+        fn from_puzzle_input(input: &str) -> Result<Self, ParseError> {
+            let mut sections = SectionReader::new(input);
+            let map = sections.parse_next("map", SquareCharacterGrid::from_puzzle_input)?;
+            let moves = sections.parse_next("moves", |s| {
+                Ok(s.chars().filter(|c| !c.is_whitespace()).collect())
+            })?;
+            sections.finish()?;
+            Ok(Warehouse { map, moves })
+        }
+    }
+
+    #[test]
+    fn test_section_reader() {
+        let warehouse = Warehouse::from_puzzle_input("#..\n.@#\n\n<^\n>v\n").unwrap();
+        assert_eq!(warehouse.map.size(), (3, 2));
+        assert_eq!(warehouse.moves, vec!['<', '^', '>', 'v']);
+        let error = Warehouse::from_puzzle_input("#..\n.@#\n").err().unwrap();
+        assert_eq!(error.section.as_deref(), Some("moves"));
+    }
+}
@@ -0,0 +1,33 @@
+//! Registry of all days which have a solution.
+
+use crate::day11::Day11;
+use crate::day12::Day12;
+use crate::day6::Day6;
+use crate::solution::Solution;
+
+/// A solved day.
+pub struct Day {
+    pub number: u8,
+    pub solution: &'static dyn Solution,
+}
+
+/// All registered days, ordered by day number.
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 6,
+        solution: &Day6,
+    },
+    Day {
+        number: 11,
+        solution: &Day11,
+    },
+    Day {
+        number: 12,
+        solution: &Day12,
+    },
+];
+
+/// Find a registered day by its number.
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,336 @@
+//! Runs many solution parts on a pool of worker threads.
+
+use crate::cache::AnswerCache;
+use crate::error::Error;
+use crate::inputs::{self, InputKind};
+use crate::registry::Day;
+use crate::solution::Answer;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// One part of one day on one input. Answers are looked up in and stored
+/// into `cache` if there is one and the day has opted in to caching.
+pub struct Job<'a> {
+    pub day: &'static Day,
+    pub part: u8,
+    pub input_name: &'a str,
+    pub input: &'a str,
+    pub cache: Option<&'a AnswerCache>,
+}
+
+/// The answer of a job and how long it took. The answer is `None` if the part
+/// is not solved, and an error if the job failed.
+/// `elapsed` is the solve time. The parse time is only known for a
+/// [crate::solution::ParsedSolution], otherwise it is included in `elapsed`.
+/// For `cached` answers, `elapsed` is the time of the cache lookup.
+pub struct JobResult {
+    pub day: u8,
+    pub part: u8,
+    pub input_name: String,
+    pub answer: Result<Option<Answer>, Error>,
+    pub parse_time: Option<Duration>,
+    pub elapsed: Duration,
+    pub cached: bool,
+}
+
+impl JobResult {
+    /// The result of a job that could not be run because of `error`.
+    fn not_run(day: u8, part: u8, input_name: &str, error: Error) -> Self {
+        JobResult {
+            day,
+            part,
+            input_name: input_name.to_string(),
+            answer: Err(error),
+            parse_time: None,
+            elapsed: Duration::ZERO,
+            cached: false,
+        }
+    }
+
+    /// Whether the job failed. A missing input is not a failure, the day
+    /// simply has not been downloaded yet.
+    pub fn failed(&self) -> bool {
+        self.answer.is_err() && !matches!(self.answer, Err(Error::MissingInput { .. }))
+    }
+}
+
+impl Job<'_> {
+    pub fn run(&self) -> JobResult {
+        let (day, part) = (self.day.number, self.part);
+        let cache = self.cache.zip(self.day.solution.version());
+        let start = Instant::now();
+        let cached = cache.and_then(|(cache, version)| cache.get(day, part, version, self.input));
+        let hit = cached.is_some();
+        let (answer, parse_time, elapsed) = match cached {
+            Some(answer) => (Ok(Some(answer)), None, start.elapsed()),
+            None => {
+                let solved = self.day.solution.solve_timed(part, self.input);
+                if let (Some((cache, version)), Ok((Some(answer), _))) = (cache, &solved) {
+                    if let Err(error) = cache.put(day, part, version, self.input, answer) {
+                        eprintln!(
+                            "Warning: cannot cache the answer of day {} part {}: {}",
+                            day, part, error
+                        );
+                    }
+                }
+                match solved {
+                    Ok((answer, timing)) => (Ok(answer), timing.parse, timing.solve),
+                    Err(error) => (Err(error), None, start.elapsed()),
+                }
+            }
+        };
+        JobResult {
+            day,
+            part,
+            input_name: self.input_name.to_string(),
+            answer,
+            parse_time,
+            elapsed,
+            cached: hit,
+        }
+    }
+
+    /// Like [Job::run], but a panicking solution gives a failed result.
+    fn run_catching_panics(&self) -> JobResult {
+        let start = Instant::now();
+        panic::catch_unwind(AssertUnwindSafe(|| self.run())).unwrap_or_else(|payload| JobResult {
+            day: self.day.number,
+            part: self.part,
+            input_name: self.input_name.to_string(),
+            answer: Err(Error::Message(format!(
+                "panicked: {}",
+                panic_message(payload.as_ref())
+            ))),
+            parse_time: None,
+            elapsed: start.elapsed(),
+            cached: false,
+        })
+    }
+}
+
+/// The message of a panic, if it has one.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "no message"
+    }
+}
+
+/// Number of worker threads to use if nothing else is configured.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Run all jobs on `threads` workers. Results are returned in the same order as the jobs.
+/// A job that panics does not stop the others, its result is an error.
+pub fn run_parallel(jobs: &[Job], threads: usize) -> Vec<JobResult> {
+    // Workers pull the next job index from a shared counter, so a slow job
+    // does not hold up the jobs queued behind it.
+    let next_job = AtomicUsize::new(0);
+    let mut results: Vec<(usize, JobResult)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.max(1))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let index = next_job.fetch_add(1, Ordering::Relaxed);
+                        let Some(job) = jobs.get(index) else {
+                            return done;
+                        };
+                        done.push((index, job.run_catching_panics()));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("worker thread panicked"))
+            .collect()
+    });
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Run `parts` of all `days` on their real inputs in `dir`, on `threads` workers.
+/// Results are ordered by day. A day whose input is missing or cannot be read
+/// is not run, it gets a failed result for each part instead.
+pub fn run_all(
+    days: &'static [Day],
+    dir: &Path,
+    parts: &[u8],
+    threads: usize,
+    cache: Option<&AnswerCache>,
+) -> Vec<JobResult> {
+    let mut inputs = vec![];
+    let mut not_run = vec![];
+    for day in days {
+        let real_inputs: Vec<_> = inputs::discover_in(dir, day.number)
+            .into_iter()
+            .filter(|input_file| input_file.kind == InputKind::Real)
+            .collect();
+        if real_inputs.is_empty() {
+            let name = format!("day{}", day.number);
+            for &part in parts {
+                let error = Error::MissingInput {
+                    path: dir.join(&name),
+                    day: Some(day.number),
+                };
+                not_run.push(JobResult::not_run(day.number, part, &name, error));
+            }
+        }
+        for input_file in real_inputs {
+            match input_file.read() {
+                Ok(input) => inputs.push((day, input_file.name, input)),
+                Err(error) => {
+                    for &part in parts {
+                        let error = Error::Message(error.to_string());
+                        not_run.push(JobResult::not_run(
+                            day.number,
+                            part,
+                            &input_file.name,
+                            error,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    let mut jobs = vec![];
+    for (day, input_name, input) in &inputs {
+        for &part in parts {
+            jobs.push(Job {
+                day,
+                part,
+                input_name,
+                input,
+                cache,
+            });
+        }
+    }
+
+    let mut results = run_parallel(&jobs, threads);
+    results.extend(not_run);
+    results.sort_by_key(|result| result.day);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::DAYS;
+    use crate::solution::Solution;
+    use crate::utils::TempPath;
+    use std::fs;
+
+    /// Panics on an empty input, otherwise counts the lines.
+    struct Fragile;
+
+    impl Solution for Fragile {
+        fn part1(&self, input: &str) -> Answer {
+            assert!(!input.is_empty(), "empty input");
+            input.lines().count().into()
+        }
+    }
+
+    static FRAGILE: Day = Day {
+        number: 99,
+        solution: &Fragile,
+    };
+
+    #[test]
+    fn test_results_keep_job_order() {
+        let jobs: Vec<Job> = DAYS
+            .iter()
+            .flat_map(|day| {
+                [1, 2].map(|part| Job {
+                    day,
+                    part,
+                    input_name: "tiny",
+                    input: "0",
+                    cache: None,
+                })
+            })
+            .collect();
+        let results = run_parallel(&jobs, 3);
+        assert_eq!(results.len(), jobs.len());
+        for (job, result) in jobs.iter().zip(&results) {
+            assert_eq!((job.day.number, job.part), (result.day, result.part));
+        }
+    }
+
+    #[test]
+    fn test_panic_fails_only_its_job() {
+        let job = |input| Job {
+            day: &FRAGILE,
+            part: 1,
+            input_name: "tiny",
+            input,
+            cache: None,
+        };
+        let jobs = [job(""), job("a\nb\n")];
+        let results = run_parallel(&jobs, 2);
+        let error = results[0].answer.as_ref().unwrap_err();
+        assert_eq!(error.to_string(), "panicked: empty input");
+        assert_eq!(
+            results[1].answer.as_ref().unwrap(),
+            &Some(Answer::Number(2))
+        );
+    }
+
+    #[test]
+    fn test_run_all() {
+        static FRAGILE_DAYS: [Day; 3] = [
+            Day {
+                number: 97,
+                solution: &Fragile,
+            },
+            Day {
+                number: 98,
+                solution: &Fragile,
+            },
+            Day {
+                number: 99,
+                solution: &Fragile,
+            },
+        ];
+        let dir = TempPath::new("run-all");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day97"), "").unwrap();
+        fs::write(dir.join("day99"), "a\nb\n").unwrap();
+        fs::write(dir.join("day99-test"), "").unwrap();
+
+        let results = run_all(&FRAGILE_DAYS, &dir, &[1, 2], 2, None);
+        let summary: Vec<_> = results
+            .iter()
+            .map(|result| (result.day, result.part, result.input_name.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (97, 1, "day97"),
+                (97, 2, "day97"),
+                (98, 1, "day98"),
+                (98, 2, "day98"),
+                (99, 1, "day99"),
+                (99, 2, "day99")
+            ]
+        );
+        // The empty input panics in part 1. Part 2 is not solved.
+        assert!(results[0].failed());
+        assert_eq!(results[1].answer.as_ref().unwrap(), &None);
+        // The missing input is reported, but it is not a failure.
+        assert!(matches!(results[2].answer, Err(Error::MissingInput { .. })));
+        assert!(!results[2].failed());
+        assert_eq!(
+            results[4].answer.as_ref().unwrap(),
+            &Some(Answer::Number(2))
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Generates the module and input files for a new day and registers it.
+
+use crate::error::Error;
+use crate::utils::{crate_root, input_dir};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+const LIB_FILE: &str = "src/lib.rs";
+const REGISTRY_FILE: &str = "src/registry.rs";
+
+/// Create `src/dayN.rs`, `input/dayN` and `input/dayN-test` and register the
+/// day. The real input goes into the input directory. Sources and the example
+/// go into the crate root, because the generated test reads the example from
+/// there. Fails without touching anything if one of the files already exists.
+/// Returns the created files.
+pub fn create_day(day: u8) -> Result<Vec<PathBuf>, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::Message(format!(
+            "Day {} is not an Advent of Code day",
+            day
+        )));
+    }
+    let root = crate_root();
+    let inputs = input_dir();
+    let examples = root.join("input");
+    let module = root.join(format!("src/day{}.rs", day));
+    let real_input = inputs.join(format!("day{}", day));
+    let example_input = examples.join(format!("day{}-test", day));
+    let lib_file = root.join(LIB_FILE);
+    let registry_file = root.join(REGISTRY_FILE);
+    let new_files = [module, real_input, example_input];
+    for path in &new_files {
+        if path.exists() {
+            return Err(Error::Message(format!(
+                "{} already exists, not overwriting it",
+                path.display()
+            )));
+        }
+    }
+
+    let lib_rs = read(&lib_file)?;
+    let registry_rs = read(&registry_file)?;
+    let lib_rs = register_module(&lib_rs, day)?;
+    let registry_rs = register_day(&registry_rs, day)?;
+
+    for dir in [&inputs, &examples] {
+        fs::create_dir_all(dir).map_err(|source| Error::File {
+            path: dir.to_path_buf(),
+            action: "create",
+            source,
+        })?;
+    }
+    write(&new_files[0], &render_template(day))?;
+    write(&new_files[1], "")?;
+    write(&new_files[2], "")?;
+    write(&lib_file, &lib_rs)?;
+    write(&registry_file, &registry_rs)?;
+    Ok(new_files.to_vec())
+}
+
+fn read(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|source| Error::File {
+        path: path.to_path_buf(),
+        action: "read",
+        source,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), Error> {
+    fs::write(path, contents).map_err(|source| Error::File {
+        path: path.to_path_buf(),
+        action: "write",
+        source,
+    })
+}
+
+fn render_template(day: u8) -> String {
+    TEMPLATE.replace("{day}", &day.to_string())
+}
+
+/// Insert `line` into the block of lines matching `is_block_line`, keeping the block sorted.
+fn insert_sorted(source: &str, line: &str, is_block_line: impl Fn(&str) -> bool) -> Option<String> {
+    let lines: Vec<&str> = source.lines().collect();
+    let block: Vec<usize> = (0..lines.len())
+        .filter(|&i| is_block_line(lines[i]))
+        .collect();
+    let position = block
+        .iter()
+        .find(|&&i| lines[i] > line)
+        .copied()
+        .or_else(|| block.last().map(|&i| i + 1))?;
+    // Keep attributes together with the line they belong to.
+    let mut position = position;
+    while position > 0 && lines[position - 1].starts_with("#[") {
+        position -= 1;
+    }
+    let mut result: Vec<&str> = lines[..position].to_vec();
+    result.push(line);
+    result.extend(&lines[position..]);
+    Some(result.join("\n") + "\n")
+}
+
+/// Add `pub mod dayN;` to the module declarations of `lib.rs`.
+fn register_module(lib_rs: &str, day: u8) -> Result<String, String> {
+    let line = format!("pub mod day{};", day);
+    if lib_rs.lines().any(|l| l == line) {
+        return Err(format!("day{} is already declared in {}", day, LIB_FILE));
+    }
+    let is_mod_line = |l: &str| l.starts_with("pub mod ") && l.ends_with(';');
+    insert_sorted(lib_rs, &line, is_mod_line)
+        .ok_or_else(|| format!("No module declarations found in {}", LIB_FILE))
+}
+
+/// Import `DayN` and add it to `DAYS` in `registry.rs`, keeping the days ordered.
+fn register_day(registry_rs: &str, day: u8) -> Result<String, String> {
+    let not_found = || format!("Cannot find the DAYS list in {}", REGISTRY_FILE);
+    let import = format!("use crate::day{}::Day{};", day, day);
+    if registry_rs.lines().any(|l| l == import) {
+        return Err(format!(
+            "Day {} is already registered in {}",
+            day, REGISTRY_FILE
+        ));
+    }
+    let is_day_import = |l: &str| l.starts_with("use crate::day");
+    let registry_rs = insert_sorted(registry_rs, &import, is_day_import).ok_or_else(not_found)?;
+
+    // Entries look like `    Day {\n        number: 6,\n ...    },`. Insert before the
+    // first entry with a larger number, or at the end of the list.
+    let lines: Vec<&str> = registry_rs.lines().collect();
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("pub const DAYS"))
+        .ok_or_else(not_found)?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| *l == "];")
+            .ok_or_else(not_found)?;
+    let position = (start..end)
+        .find(|&i| {
+            let number = lines[i]
+                .trim()
+                .strip_prefix("number: ")
+                .and_then(|n| n.trim_end_matches(',').parse::<u8>().ok());
+            number.is_some_and(|n| n > day)
+        })
+        // The entry starts on the line before its number.
+        .map_or(end, |i| i - 1);
+    let entry = format!(
+        "    Day {{\n        number: {},\n        solution: &Day{},\n    }},",
+        day, day
+    );
+    let mut result: Vec<&str> = lines[..position].to_vec();
+    result.push(&entry);
+    result.extend(&lines[position..]);
+    Ok(result.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_module() {
+        let lib_rs = "//! Docs\n\npub mod bench;\npub mod day11;\npub mod day6;\n#[cfg(test)]\npub mod utils;\n";
+        assert_eq!(
+            register_module(lib_rs, 7).unwrap(),
+            "//! Docs\n\npub mod bench;\npub mod day11;\npub mod day6;\npub mod day7;\n#[cfg(test)]\npub mod utils;\n"
+        );
+        assert_eq!(
+            register_module(lib_rs, 10).unwrap(),
+            "//! Docs\n\npub mod bench;\npub mod day10;\npub mod day11;\npub mod day6;\n#[cfg(test)]\npub mod utils;\n"
+        );
+        assert!(register_module(lib_rs, 6).is_err());
+        assert!(
+            register_module("pub mod cli;\n#[cfg(test)]\npub mod utils;\n", 7)
+                .unwrap()
+                .starts_with("pub mod cli;\npub mod day7;\n#[cfg(test)]\npub mod utils;")
+        );
+    }
+
+    const REGISTRY: &str = "\
+use crate::day11::Day11;
+use crate::day6::Day6;
+use crate::solution::Solution;
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 6,
+        solution: &Day6,
+    },
+    Day {
+        number: 11,
+        solution: &Day11,
+    },
+];
+";
+
+    #[test]
+    fn test_register_day() {
+        let registered = register_day(REGISTRY, 7).unwrap();
+        assert!(registered.contains("use crate::day6::Day6;\nuse crate::day7::Day7;\n"));
+        assert!(registered.contains(
+            "        solution: &Day6,\n    },\n    Day {\n        number: 7,\n        solution: &Day7,\n    },\n    Day {\n        number: 11,"
+        ));
+        let registered = register_day(REGISTRY, 25).unwrap();
+        assert!(registered.contains("        number: 25,\n        solution: &Day25,\n    },\n];"));
+        assert!(register_day(REGISTRY, 11).is_err());
+    }
+
+    #[test]
+    fn test_render_template() {
+        let module = render_template(13);
+        assert!(module.contains("pub struct Day13;"));
+        assert!(module.contains("crate_root().join(\"input/day13-test\"), Some(13))"));
+        assert!(!module.contains("{day}"));
+    }
+}
@@ -0,0 +1,200 @@
+//! The common interface of all daily solutions.
+
+use crate::error::Error;
+use crate::puzzle_input::FromPuzzleInput;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// The answer to one part of a puzzle.
+///
+/// Most answers are numbers, but some puzzles ask for a string instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Number(n as i128)
+            }
+        })*
+    };
+}
+
+answer_from_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64, usize, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// A solution for a single day. Both parts get the full puzzle input.
+pub trait Solution: Sync {
+    fn part1(&self, input: &str) -> Answer;
+
+    /// Part 2 is only revealed after part 1 is solved, so it may be missing.
+    fn part2(&self, _input: &str) -> Option<Answer> {
+        None
+    }
+
+    /// Solutions that return a version have their answers cached, see
+    /// [crate::cache]. Bump the version whenever a change to the solution
+    /// could change its answers.
+    fn version(&self) -> Option<u32> {
+        None
+    }
+
+    /// Solve a part by its number. Returns `None` if there is no such part,
+    /// and an error if the input cannot be parsed.
+    fn solve(&self, part: u8, input: &str) -> Result<Option<Answer>, Error> {
+        Ok(match part {
+            1 => Some(self.part1(input)),
+            2 => self.part2(input),
+            _ => None,
+        })
+    }
+
+    /// Like [Solution::solve], but also measures how long it took.
+    fn solve_timed(&self, part: u8, input: &str) -> Result<(Option<Answer>, Timing), Error> {
+        let start = Instant::now();
+        let answer = self.solve(part, input)?;
+        let timing = Timing {
+            parse: None,
+            solve: start.elapsed(),
+        };
+        Ok((answer, timing))
+    }
+}
+
+/// How long solving a part took. The parse time is only known for a
+/// [ParsedSolution], otherwise parsing is part of the solve time.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Timing {
+    pub parse: Option<Duration>,
+    pub solve: Duration,
+}
+
+impl Timing {
+    pub fn total(&self) -> Duration {
+        self.parse.unwrap_or_default() + self.solve
+    }
+}
+
+/// A solution that gets its input already parsed into [ParsedSolution::Input].
+/// Every `ParsedSolution` is a [Solution]. If the input cannot be parsed,
+/// [Solution::solve] returns the parse error, while calling
+/// [Solution::part1] or [Solution::part2] directly panics.
+pub trait ParsedSolution: Sync {
+    type Input: FromPuzzleInput;
+
+    /// See [Solution::version].
+    fn version(&self) -> Option<u32> {
+        None
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer;
+
+    fn part2(&self, _input: &Self::Input) -> Option<Answer> {
+        None
+    }
+}
+
+fn parse_input<T: FromPuzzleInput>(input: &str) -> T {
+    T::from_puzzle_input(input).unwrap_or_else(|error| panic!("Cannot parse input: {}", error))
+}
+
+impl<S: ParsedSolution> Solution for S {
+    fn version(&self) -> Option<u32> {
+        ParsedSolution::version(self)
+    }
+
+    fn part1(&self, input: &str) -> Answer {
+        ParsedSolution::part1(self, &parse_input(input))
+    }
+
+    fn part2(&self, input: &str) -> Option<Answer> {
+        ParsedSolution::part2(self, &parse_input(input))
+    }
+
+    fn solve(&self, part: u8, input: &str) -> Result<Option<Answer>, Error> {
+        Ok(self.solve_timed(part, input)?.0)
+    }
+
+    fn solve_timed(&self, part: u8, input: &str) -> Result<(Option<Answer>, Timing), Error> {
+        if !(1..=2).contains(&part) {
+            return Ok((None, Timing::default()));
+        }
+        let start = Instant::now();
+        let input = S::Input::from_puzzle_input(input)?;
+        let parse = start.elapsed();
+        let start = Instant::now();
+        let answer = match part {
+            1 => Some(ParsedSolution::part1(self, &input)),
+            _ => ParsedSolution::part2(self, &input),
+        };
+        let timing = Timing {
+            parse: Some(parse),
+            solve: start.elapsed(),
+        };
+        Ok((answer, timing))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(5199usize).to_string(), "5199");
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(Answer::from("ABC").to_string(), "ABC");
+    }
+
+    struct Sum;
+
+    impl ParsedSolution for Sum {
+        type Input = Vec<Vec<i64>>;
+
+        fn part1(&self, input: &Self::Input) -> Answer {
+            input.iter().flatten().sum::<i64>().into()
+        }
+    }
+
+    #[test]
+    fn test_parsed_solution() {
+        assert_eq!(Sum.solve(1, "1 2\n-4\n").unwrap(), Some(Answer::Number(-1)));
+        assert_eq!(Sum.solve(2, "1 2\n-4\n").unwrap(), None);
+        let (answer, timing) = Sum.solve_timed(1, "1 2\n-4\n").unwrap();
+        assert_eq!(answer, Some(Answer::Number(-1)));
+        assert!(timing.parse.is_some());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = Sum.solve(1, "1 2\nx\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Cannot parse input: line 2, column 1: cannot parse 'x': invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,254 @@
+//! A read-only grid that borrows the bytes of the input instead of copying them.
+
+use super::parse::ParseError;
+use super::square_grid::Grid;
+use std::marker::PhantomData;
+
+/// A grid view of an ASCII input. Row `y` starts at byte `y * stride`, where
+/// the stride is the width plus the length of the line ending.
+///
+/// Like [super::square_grid::SquareCharacterGrid::new], a byte order mark,
+/// CRLF line endings and trailing blank lines are accepted. To change cells,
+/// convert the view into a [Grid] with [GridView::to_grid].
+///
+/// Cells are bytes by default. A [CharGridView] has `char` cells instead, so
+/// it can replace a [super::square_grid::SquareCharacterGrid] without
+/// changing the code that reads the cells.
+#[derive(Debug, Clone, Copy)]
+pub struct GridView<'a, Coord: num::PrimInt, Cell = u8> {
+    bytes: &'a [u8],
+    width: usize,
+    stride: usize,
+    w: Coord,
+    h: Coord,
+    cell: PhantomData<Cell>,
+}
+
+/// A grid view of the characters of the puzzle input.
+pub type CharGridView<'a, Coord> = GridView<'a, Coord, char>;
+
+impl<'a, Coord: num::PrimInt, Cell: Copy + From<u8>> GridView<'a, Coord, Cell> {
+    /// Panics if the input is not a valid grid, see [GridView::try_new].
+    pub fn new(input: &'a str) -> Self {
+        Self::try_new(input).unwrap_or_else(|error| panic!("Invalid grid: {}", error))
+    }
+
+    /// Fails if the input is empty, not ASCII, if a row is not as wide as the
+    /// first row, or if the width or height does not fit into `Coord`.
+    pub fn try_new(input: &'a str) -> Result<Self, ParseError> {
+        let error = |line: usize, column: usize, message: String| ParseError {
+            section: None,
+            line,
+            column,
+            message,
+        };
+        let bytes = input.strip_prefix('\u{feff}').unwrap_or(input).as_bytes();
+        let first_line = bytes
+            .iter()
+            .position(|&b| b == b'\n')
+            .unwrap_or(bytes.len());
+        let crlf = bytes[..first_line].ends_with(b"\r");
+        let width = first_line - usize::from(crlf);
+        let stride = width + if crlf { 2 } else { 1 };
+        if width == 0 {
+            return Err(error(1, 1, "grid is empty".to_string()));
+        }
+
+        let mut height = 0;
+        let mut start = 0;
+        // Everything after the last row may only be empty lines.
+        while start < bytes.len() && !bytes[start..].iter().all(|&b| b == b'\n' || b == b'\r') {
+            let line = height + 1;
+            let rest = &bytes[start..];
+            let row_width = rest
+                .iter()
+                .position(|&b| b == b'\n' || b == b'\r')
+                .unwrap_or(rest.len());
+            if let Some(column) = rest[..row_width].iter().position(|b| !b.is_ascii()) {
+                return Err(error(
+                    line,
+                    column + 1,
+                    "not an ASCII character".to_string(),
+                ));
+            }
+            if row_width != width {
+                return Err(error(
+                    line,
+                    row_width.min(width) + 1,
+                    format!(
+                        "row has width {}, but the first row has width {}",
+                        row_width, width
+                    ),
+                ));
+            }
+            let ending = &rest[width..rest.len().min(stride)];
+            if !ending.is_empty() && ending != &b"\r\n"[2 - (stride - width)..] {
+                return Err(error(
+                    line,
+                    width + 1,
+                    "line ending differs from the first line".to_string(),
+                ));
+            }
+            height += 1;
+            start += stride;
+        }
+
+        let w = Coord::from(width).ok_or_else(|| {
+            error(
+                1,
+                1,
+                format!("width {} does not fit the coordinate type", width),
+            )
+        })?;
+        let h = Coord::from(height).ok_or_else(|| {
+            error(
+                1,
+                1,
+                format!("height {} does not fit the coordinate type", height),
+            )
+        })?;
+        Ok(GridView {
+            bytes,
+            width,
+            stride,
+            w,
+            h,
+            cell: PhantomData,
+        })
+    }
+
+    pub fn size(&self) -> (Coord, Coord) {
+        (self.w, self.h)
+    }
+
+    /// Get the cell at a specific coordinate.
+    pub fn get(&self, x: Coord, y: Coord) -> Option<Cell> {
+        if x >= self.w || y >= self.h {
+            return None;
+        }
+        // Negative coordinates do not convert.
+        let (x, y) = (x.to_usize()?, y.to_usize()?);
+        Some(self.bytes[y * self.stride + x].into())
+    }
+
+    /// The bytes of row `y`, without the line ending.
+    pub fn row(&self, y: Coord) -> Option<&'a [u8]> {
+        if y >= self.h {
+            return None;
+        }
+        let start = y.to_usize()? * self.stride;
+        Some(&self.bytes[start..start + self.width])
+    }
+
+    /// Get an iterator over the grid, yielding `(x, y, cell)`.
+    pub fn iter(&self) -> GridViewIterator<'a, Coord, Cell> {
+        GridViewIterator {
+            view: *self,
+            x: Coord::zero(),
+            y: Coord::zero(),
+        }
+    }
+
+    /// Copy the view into an owned grid, which can be changed.
+    pub fn to_grid(&self) -> Grid<Cell, Coord> {
+        Grid::from_fn(self.w, self.h, |x, y| self.get(x, y).expect("same shape"))
+    }
+}
+
+pub struct GridViewIterator<'a, Coord: num::PrimInt, Cell = u8> {
+    view: GridView<'a, Coord, Cell>,
+    x: Coord,
+    y: Coord,
+}
+
+impl<Coord: num::PrimInt, Cell: Copy + From<u8>> Iterator for GridViewIterator<'_, Coord, Cell> {
+    type Item = (Coord, Coord, Cell);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let b = self.view.get(self.x, self.y)?;
+        let result = (self.x, self.y, b);
+
+        // Step one forward, check for linebreaks.
+        self.x = self.x + Coord::one();
+        if self.x >= self.view.w {
+            self.x = Coord::zero();
+            self.y = self.y + Coord::one();
+        }
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_view() {
+        let input = "..#\n#..\n";
+        let view: GridView<i32> = GridView::new(input);
+        assert_eq!(view.size(), (3, 2));
+        assert_eq!(view.get(2, 0), Some(b'#'));
+        assert_eq!(view.get(0, 1), Some(b'#'));
+        assert_eq!(view.get(3, 0), None);
+        assert_eq!(view.get(-1, 0), None);
+        assert_eq!(view.get(0, 2), None);
+        assert_eq!(view.row(1), Some(&b"#.."[..]));
+        let hashes: Vec<(i32, i32)> = view
+            .iter()
+            .filter(|(_, _, b)| *b == b'#')
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        assert_eq!(hashes, vec![(2, 0), (0, 1)]);
+
+        let mut grid = view.to_grid();
+        grid.set(0, 0, b'@');
+        assert_eq!(grid.get(0, 0), Some(b'@'));
+        assert_eq!(view.get(0, 0), Some(b'.'));
+    }
+
+    #[test]
+    fn test_char_grid_view() {
+        use crate::utils::square_grid::SquareCharacterGrid;
+
+        let input = "..#\n#.^\n";
+        let view: CharGridView<i32> = GridView::new(input);
+        let grid: SquareCharacterGrid<i32> = SquareCharacterGrid::new(input);
+        assert_eq!(view.size(), grid.size());
+        assert_eq!(view.get(2, 1), Some('^'));
+        assert_eq!(view.get(-1, 0), grid.get(-1, 0));
+        assert!(view.iter().eq(grid.iter()));
+        assert_eq!(view.to_grid(), grid);
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let view: GridView<i32> = GridView::new("\u{feff}ab\r\ncd\r\n\r\n");
+        assert_eq!(view.size(), (2, 2));
+        assert_eq!(view.get(1, 1), Some(b'd'));
+        let view: GridView<i32> = GridView::new("ab\ncd");
+        assert_eq!(view.get(1, 1), Some(b'd'));
+        // Spaces are cells, not blank lines.
+        let view: GridView<i32> = GridView::new("#.\n  \n\n");
+        assert_eq!(view.size(), (2, 2));
+        assert_eq!(view.row(1), Some(&b"  "[..]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |input| GridView::<i8>::try_new(input).unwrap_err().to_string();
+        assert_eq!(
+            error("ab\nc\nde"),
+            "line 2, column 2: row has width 1, but the first row has width 2"
+        );
+        assert_eq!(
+            error("ab\r\ncd\nef"),
+            "line 2, column 3: line ending differs from the first line"
+        );
+        assert_eq!(error("\n"), "line 1, column 1: grid is empty");
+        assert_eq!(
+            error("ab\n\u{e9}b"),
+            "line 2, column 1: not an ASCII character"
+        );
+        assert!(GridView::<i8>::try_new(&".".repeat(128)).is_err());
+    }
+}
@@ -1,11 +1,185 @@
-use std::fs::File;
-use std::io::Read;
+use crate::error::Error;
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, ErrorKind, Read};
+use std::path::{self, Path, PathBuf};
+use std::sync::OnceLock;
 
+pub mod grid_view;
+pub mod normalize;
+pub mod parse;
+pub mod pattern;
 pub mod square_grid;
 
-pub fn read_file(filename: &str) -> String {
-    let mut input = String::new();
-    let mut file = File::open(format!("input/{}", filename)).unwrap();
-    file.read_to_string(&mut input).unwrap();
-    input
-}
\ No newline at end of file
+/// Environment variable to read the input directory from.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+/// Passing this as a path reads the input from stdin.
+pub const STDIN_PATH: &str = "-";
+
+static INPUT_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// The root directory of this crate, which has the answers file and the sources.
+pub fn crate_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Use `dir` as the input directory, taking precedence over the environment.
+/// Only the first call has an effect.
+pub fn set_input_dir(dir: PathBuf) {
+    let _ = INPUT_DIR_OVERRIDE.set(dir);
+}
+
+/// The directory with the input files. This is the directory given to
+/// [set_input_dir], or the one from `AOC_INPUT_DIR`, or `input` in the crate
+/// root, so the binary works from any working directory.
+pub fn input_dir() -> PathBuf {
+    if let Some(dir) = INPUT_DIR_OVERRIDE.get() {
+        return dir.clone();
+    }
+    match std::env::var_os(INPUT_DIR_ENV) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => crate_root().join("input"),
+    }
+}
+
+/// Read a file from the input directory. The day is taken from a `dayN` file name.
+pub fn read_file(filename: &str) -> Result<String, Error> {
+    read_path(&input_dir().join(filename), day_from_file_name(filename))
+}
+
+/// Read an input file, with errors naming the resolved path and the day.
+/// The path `-` reads from stdin. The input is [normalize::normalize]d.
+pub fn read_path(path: &Path, day: Option<u8>) -> Result<String, Error> {
+    let (path, bytes) = if path == Path::new(STDIN_PATH) {
+        let mut bytes = vec![];
+        let read = io::stdin().read_to_end(&mut bytes);
+        (path.to_path_buf(), read.map(|_| bytes))
+    } else {
+        let path = path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        let read = fs::read(&path);
+        (path, read)
+    };
+    let bytes = bytes.map_err(|source| match source.kind() {
+        ErrorKind::NotFound => Error::MissingInput {
+            path: path.clone(),
+            day,
+        },
+        _ => Error::UnreadableInput {
+            path: path.clone(),
+            day,
+            source,
+        },
+    })?;
+    let input = String::from_utf8(bytes).map_err(|e| Error::InputNotUtf8 {
+        path: path.clone(),
+        day,
+        valid_up_to: e.utf8_error().valid_up_to(),
+    })?;
+    let source = if path == Path::new(STDIN_PATH) {
+        "stdin".to_string()
+    } else {
+        path.display().to_string()
+    };
+    match normalize::normalize(&input, &source) {
+        Cow::Borrowed(_) => Ok(input),
+        Cow::Owned(normalized) => Ok(normalized),
+    }
+}
+
+/// A unique path in the temp directory for tests, which is deleted when the
+/// guard is dropped, also if an assertion fails first. Nothing is created.
+#[cfg(test)]
+pub(crate) struct TempPath(PathBuf);
+
+#[cfg(test)]
+impl TempPath {
+    pub(crate) fn new(name: &str) -> Self {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let unique = NEXT.fetch_add(1, Ordering::Relaxed);
+        let file_name = format!("aoc2024-{}-{}-{}", name, std::process::id(), unique);
+        let path = std::env::temp_dir().join(file_name);
+        remove_path(&path);
+        TempPath(path)
+    }
+}
+
+#[cfg(test)]
+impl std::ops::Deref for TempPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl AsRef<Path> for TempPath {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        remove_path(&self.0);
+    }
+}
+
+/// Remove a file or a directory with its contents, if it exists.
+#[cfg(test)]
+fn remove_path(path: &Path) {
+    let _ = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+}
+
+/// `day12-test-b` belongs to day 12.
+fn day_from_file_name(filename: &str) -> Option<u8> {
+    let digits: String = filename
+        .strip_prefix("day")?
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_file_errors() {
+        let error = read_file("day99-test-does-not-exist").unwrap_err();
+        assert!(matches!(error, Error::MissingInput { day: Some(99), .. }));
+        let message = error.to_string();
+        let expected = path::absolute(input_dir().join("day99-test-does-not-exist")).unwrap();
+        assert!(message.contains(&expected.display().to_string()));
+        assert!(message.contains("for day 99"));
+
+        let path = TempPath::new("latin1");
+        fs::write(&path, b"ok\xe9").unwrap();
+        let error = read_path(&path, None).unwrap_err();
+        assert!(matches!(error, Error::InputNotUtf8 { valid_up_to: 2, .. }));
+
+        let path = TempPath::new("crlf");
+        fs::write(&path, "\u{feff}ab\r\ncd\r\n\r\n").unwrap();
+        assert_eq!(read_path(&path, None).unwrap(), "ab\ncd\n");
+
+        assert!(matches!(
+            read_path(&std::env::temp_dir(), Some(1)),
+            Err(Error::UnreadableInput { .. })
+        ));
+    }
+
+    #[test]
+    fn test_day_from_file_name() {
+        assert_eq!(day_from_file_name("day12-test-b"), Some(12));
+        assert_eq!(day_from_file_name("day6"), Some(6));
+        assert_eq!(day_from_file_name("aoc1-in2.txt"), None);
+    }
+}
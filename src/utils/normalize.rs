@@ -0,0 +1,141 @@
+//! Input normalization: byte order marks, CRLF line endings and trailing blank lines.
+//!
+//! Editors on some platforms add these, and they end up as extra grid cells or
+//! empty rows. All input loading goes through [normalize].
+
+use std::borrow::Cow;
+use std::fmt;
+
+/// What normalizing an input changed.
+#[derive(Debug, Default, PartialEq)]
+pub struct Changes {
+    pub removed_bom: bool,
+    pub converted_crlf: usize,
+    pub trimmed_blank_lines: usize,
+}
+
+impl Changes {
+    pub fn is_empty(&self) -> bool {
+        *self == Changes::default()
+    }
+}
+
+impl fmt::Display for Changes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = vec![];
+        if self.removed_bom {
+            parts.push("removed the byte order mark".to_string());
+        }
+        if self.converted_crlf > 0 {
+            parts.push(count(self.converted_crlf, "CRLF line ending", "converted"));
+        }
+        if self.trimmed_blank_lines > 0 {
+            parts.push(count(
+                self.trimmed_blank_lines,
+                "trailing blank line",
+                "trimmed",
+            ));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// `converted 1 CRLF line ending`, `converted 2 CRLF line endings`.
+fn count(n: usize, noun: &str, verb: &str) -> String {
+    let plural = if n == 1 { "" } else { "s" };
+    format!("{} {} {}{}", verb, n, noun, plural)
+}
+
+/// Normalize an input without reporting anything. Borrows the input if nothing changes.
+///
+/// Only empty lines count as blank, so spaces in the last line are kept. A
+/// single final newline is kept if the input ends with one.
+pub fn normalize_quietly(input: &str) -> (Cow<'_, str>, Changes) {
+    let mut changes = Changes::default();
+    let mut text = Cow::Borrowed(input);
+
+    if let Some(rest) = text.strip_prefix('\u{feff}') {
+        changes.removed_bom = true;
+        text = Cow::Owned(rest.to_string());
+    }
+
+    changes.converted_crlf = text.matches("\r\n").count();
+    if changes.converted_crlf > 0 {
+        text = Cow::Owned(text.replace("\r\n", "\n"));
+    }
+
+    let content = text.trim_end_matches('\n');
+    let had_final_newline = text.ends_with('\n');
+    changes.trimmed_blank_lines = text.len() - content.len();
+    if had_final_newline && !content.is_empty() {
+        // The newline ending the last line is not a blank line.
+        changes.trimmed_blank_lines -= 1;
+    }
+    if changes.trimmed_blank_lines > 0 {
+        let mut trimmed = content.to_string();
+        if had_final_newline && !trimmed.is_empty() {
+            trimmed.push('\n');
+        }
+        text = Cow::Owned(trimmed);
+    }
+
+    (text, changes)
+}
+
+/// Normalize an input and print a warning naming `source` if anything changed.
+pub fn normalize<'a>(input: &'a str, source: &str) -> Cow<'a, str> {
+    let (text, changes) = normalize_quietly(input);
+    if !changes.is_empty() {
+        eprintln!("Warning: normalized {}: {}", source, changes);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_input_is_borrowed() {
+        let (text, changes) = normalize_quietly("..#\n#..\n");
+        assert!(matches!(text, Cow::Borrowed("..#\n#..\n")));
+        assert!(changes.is_empty());
+        assert!(normalize_quietly("125 17").1.is_empty());
+    }
+
+    #[test]
+    fn test_normalize() {
+        let (text, changes) = normalize_quietly("\u{feff}..#\r\n#..\r\n\r\n\n");
+        assert_eq!(text, "..#\n#..\n");
+        assert_eq!(
+            changes,
+            Changes {
+                removed_bom: true,
+                converted_crlf: 3,
+                trimmed_blank_lines: 2
+            }
+        );
+        assert_eq!(
+            changes.to_string(),
+            "removed the byte order mark, converted 3 CRLF line endings, trimmed 2 trailing blank lines"
+        );
+    }
+
+    #[test]
+    fn test_trailing_blank_lines() {
+        let (text, changes) = normalize_quietly("ab\n\n");
+        assert_eq!(text, "ab\n");
+        assert_eq!(changes.to_string(), "trimmed 1 trailing blank line");
+        let (text, changes) = normalize_quietly("\n\n");
+        assert_eq!(text, "");
+        assert_eq!(changes.trimmed_blank_lines, 2);
+    }
+
+    #[test]
+    fn test_spaces_in_last_line_are_kept() {
+        let (text, changes) = normalize_quietly("a \nb \n\n");
+        assert_eq!(text, "a \nb \n");
+        assert_eq!(changes.trimmed_blank_lines, 1);
+        assert!(normalize_quietly("ab\n \t").1.is_empty());
+    }
+}
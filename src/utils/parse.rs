@@ -0,0 +1,374 @@
+//! Parsers for the line-oriented inputs that come up every year.
+//!
+//! All of them report the line and column of bad input. Line and column
+//! numbers start at 1 and count characters, not bytes.
+
+use super::normalize::normalize;
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+/// Bad input at a line and column, in a named section of the input if it
+/// has sections.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub section: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// An error in a single line, at the byte offset `offset` of `line`.
+    pub(crate) fn in_line(line: &str, offset: usize, message: String) -> Self {
+        ParseError {
+            section: None,
+            line: 1,
+            column: line[..offset].chars().count() + 1,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(section) = &self.section {
+            write!(f, "section '{}', ", section)?;
+        }
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse every line of `input` with `parse_line`. Errors from `parse_line`
+/// are moved to the line they happened on.
+pub fn lines_with<T>(
+    input: &str,
+    mut parse_line: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    normalize(input, "parser input")
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            parse_line(line).map_err(|error| ParseError {
+                line: error.line + index,
+                ..error
+            })
+        })
+        .collect()
+}
+
+/// The sections of `input`, which are separated by one or more blank lines,
+/// with the number of lines before each section.
+pub fn split_sections(input: &str) -> Vec<(usize, &str)> {
+    let mut sections = vec![];
+    let mut start: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for (index, line) in input.split_inclusive('\n').enumerate() {
+        if line.trim().is_empty() {
+            if let Some((first_line, start_offset)) = start.take() {
+                sections.push((first_line, &input[start_offset..offset]));
+            }
+        } else if start.is_none() {
+            start = Some((index, offset));
+        }
+        offset += line.len();
+    }
+    if let Some((first_line, start_offset)) = start {
+        sections.push((first_line, &input[start_offset..]));
+    }
+    sections
+}
+
+/// Parses an input section by section, each with its own parser. Errors
+/// name the section and count lines from the start of the input.
+///
+/// ```
+/// # use aoc2024::utils::parse::{self, SectionReader};
+/// let mut sections = SectionReader::new("47|53\n97|13\n\n75,47,61\n");
+/// let rules = sections.parse_next("rules", |s| {
+///     parse::lines_with(s, parse::integers::<u8>)
+/// })?;
+/// let updates = sections.parse_next("updates", |s| {
+///     parse::lines_with(s, parse::comma_separated::<u8>)
+/// })?;
+/// sections.finish()?;
+/// assert_eq!(rules, vec![vec![47, 53], vec![97, 13]]);
+/// assert_eq!(updates, vec![vec![75, 47, 61]]);
+/// # Ok::<(), parse::ParseError>(())
+/// ```
+pub struct SectionReader<'a> {
+    input: Cow<'a, str>,
+    /// Remaining sections as the number of lines before them and their byte range.
+    sections: Vec<(usize, std::ops::Range<usize>)>,
+    next: usize,
+}
+
+impl<'a> SectionReader<'a> {
+    pub fn new(input: &'a str) -> Self {
+        let input = normalize(input, "parser input");
+        let sections = split_sections(&input)
+            .into_iter()
+            .map(|(first_line, section)| {
+                let start = section.as_ptr() as usize - input.as_ptr() as usize;
+                (first_line, start..start + section.len())
+            })
+            .collect();
+        SectionReader {
+            input,
+            sections,
+            next: 0,
+        }
+    }
+
+    /// Number of sections not parsed yet.
+    pub fn remaining(&self) -> usize {
+        self.sections.len() - self.next
+    }
+
+    /// Parse the next section with `parser`. `name` is used in errors.
+    pub fn parse_next<T>(
+        &mut self,
+        name: &str,
+        parser: impl FnOnce(&str) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let Some((first_line, range)) = self.sections.get(self.next).cloned() else {
+            return Err(ParseError {
+                section: Some(name.to_string()),
+                line: self.input.lines().count() + 1,
+                column: 1,
+                message: "missing section, the input ends before it".to_string(),
+            });
+        };
+        self.next += 1;
+        parser(&self.input[range]).map_err(|error| ParseError {
+            section: Some(name.to_string()),
+            line: error.line + first_line,
+            ..error
+        })
+    }
+
+    /// Check that all sections were parsed.
+    pub fn finish(self) -> Result<(), ParseError> {
+        match self.sections.get(self.next) {
+            None => Ok(()),
+            Some((first_line, _)) => Err(ParseError {
+                section: None,
+                line: first_line + 1,
+                column: 1,
+                message: format!("unexpected section, expected only {} sections", self.next),
+            }),
+        }
+    }
+}
+
+/// All signed integers in a line, ignoring any other text: `p=0,4 v=3,-3`
+/// gives `[0, 4, 3, -3]`. A `-` or `+` directly after a digit is not a
+/// sign, so the range `1-3` gives `[1, 3]`.
+pub fn integers<T>(line: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let bytes = line.as_bytes();
+    let mut numbers = vec![];
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let is_sign = matches!(bytes[offset], b'-' | b'+')
+            && bytes.get(offset + 1).is_some_and(u8::is_ascii_digit)
+            && (offset == 0 || !bytes[offset - 1].is_ascii_digit());
+        if !is_sign && !bytes[offset].is_ascii_digit() {
+            offset += 1;
+            continue;
+        }
+        let start = offset;
+        offset += 1;
+        while offset < bytes.len() && bytes[offset].is_ascii_digit() {
+            offset += 1;
+        }
+        numbers.push(parse_at(line, start, &line[start..offset])?);
+    }
+    Ok(numbers)
+}
+
+/// The whitespace-separated values of a line. Unlike [integers], every
+/// value has to parse.
+pub fn numbers<T>(line: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fields(line, char::is_whitespace)
+}
+
+/// The comma-separated values of a line, like `3,-1, 4`. Spaces around the
+/// values are ignored.
+pub fn comma_separated<T>(line: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    if line.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    let mut values = vec![];
+    let mut start = 0;
+    for field in line.split(',') {
+        let trimmed = field.trim_start();
+        let offset = start + field.len() - trimmed.len();
+        values.push(parse_at(line, offset, trimmed.trim_end())?);
+        start += field.len() + 1;
+    }
+    Ok(values)
+}
+
+/// Exactly `N` whitespace-separated values on every line of `input`.
+pub fn tuples<const N: usize, T>(input: &str) -> Result<Vec<[T; N]>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    lines_with(input, |line| {
+        let values: Vec<T> = numbers(line)?;
+        let found = values.len();
+        values.try_into().map_err(|_| {
+            ParseError::in_line(
+                line,
+                line.len(),
+                format!("expected {} values, found {}", N, found),
+            )
+        })
+    })
+}
+
+/// The columns of `input`, each line having `N` whitespace-separated values:
+/// `"3 4\n4 3"` gives `[[3, 4], [4, 3]]`.
+pub fn columns<const N: usize, T>(input: &str) -> Result<[Vec<T>; N], ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let mut columns: [Vec<T>; N] = std::array::from_fn(|_| vec![]);
+    for row in tuples::<N, T>(input)? {
+        for (column, value) in columns.iter_mut().zip(row) {
+            column.push(value);
+        }
+    }
+    Ok(columns)
+}
+
+/// The fields of a line between separators, each parsed as `T`.
+fn fields<T>(line: &str, is_separator: impl Fn(char) -> bool) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let mut values = vec![];
+    let mut offset = 0;
+    for field in line.split(&is_separator) {
+        if !field.is_empty() {
+            values.push(parse_at(line, offset, field)?);
+        }
+        offset += field.len();
+        // Skip the separator, which may be longer than one byte.
+        offset += line[offset..].chars().next().map_or(0, char::len_utf8);
+    }
+    Ok(values)
+}
+
+/// Parse `text`, which starts at byte `offset` of `line`.
+pub(crate) fn parse_at<T>(line: &str, offset: usize, text: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    text.parse()
+        .map_err(|e| ParseError::in_line(line, offset, format!("cannot parse '{}': {}", text, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(integers::<i32>("p=0,4 v=3,-3"), Ok(vec![0, 4, 3, -3]));
+        assert_eq!(integers::<i64>("Button A: X+94, Y-34"), Ok(vec![94, -34]));
+        assert_eq!(integers::<u8>("1-3 a: abc"), Ok(vec![1, 3]));
+        assert_eq!(integers::<i32>("no numbers"), Ok(vec![]));
+        let error = integers::<u8>("x=12, y=300").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 9));
+    }
+
+    #[test]
+    fn test_numbers_and_comma_separated() {
+        assert_eq!(numbers::<i64>("7 6  4 2 1"), Ok(vec![7, 6, 4, 2, 1]));
+        let error = numbers::<i64>("7 6 x4").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 5: cannot parse 'x4': invalid digit found in string"
+        );
+        assert_eq!(comma_separated::<i32>("3,-1, 4"), Ok(vec![3, -1, 4]));
+        assert_eq!(comma_separated::<i32>(""), Ok(vec![]));
+        assert_eq!(comma_separated::<i32>("1,,2").unwrap_err().column, 3);
+    }
+
+    #[test]
+    fn test_tuples_and_columns() {
+        let input = "3   4\n4   3\n2   5\n";
+        assert_eq!(tuples::<2, u32>(input), Ok(vec![[3, 4], [4, 3], [2, 5]]));
+        assert_eq!(columns::<2, u32>(input), Ok([vec![3, 4, 2], vec![4, 3, 5]]));
+
+        let error = tuples::<2, u32>("3 4\n4 3 1\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 6: expected 2 values, found 3"
+        );
+        let error = columns::<2, u32>("3 4\n4 ü\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn test_split_sections() {
+        assert_eq!(
+            split_sections("a\nb\n\n\nc\n \nd"),
+            vec![(0, "a\nb\n"), (4, "c\n"), (6, "d")]
+        );
+        assert_eq!(split_sections("\n\n"), vec![]);
+    }
+
+    #[test]
+    fn test_section_reader_errors() {
+        let input = "1 2\n\n3 x\n";
+        let mut sections = SectionReader::new(input);
+        assert_eq!(
+            sections.parse_next("first", tuples::<2, u8>),
+            Ok(vec![[1, 2]])
+        );
+        let error = sections.parse_next("second", tuples::<2, u8>).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "section 'second', line 3, column 3: cannot parse 'x': invalid digit found in string"
+        );
+        let error = sections.parse_next("third", tuples::<2, u8>).unwrap_err();
+        assert_eq!((error.section.as_deref(), error.line), (Some("third"), 4));
+
+        let mut sections = SectionReader::new(input);
+        sections.parse_next("first", tuples::<2, u8>).unwrap();
+        assert_eq!(sections.finish().unwrap_err().line, 3);
+    }
+
+    #[test]
+    fn test_lines_with() {
+        assert_eq!(
+            lines_with("1,2\r\n3\r\n", comma_separated::<u8>),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(lines_with("1\n2\nb\n", numbers::<u8>).unwrap_err().line, 3);
+    }
+}
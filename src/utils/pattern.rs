@@ -0,0 +1,224 @@
+//! Matching lines against templates like `"Button A: X+{}, Y+{}"`, as a
+//! small replacement for regular expressions.
+//!
+//! Every `{}` in a template captures text up to the first occurrence of the
+//! literal text after it, or up to the end of the line if it is the last
+//! thing in the template. Literal text has to match exactly, including spaces.
+
+use super::parse::{parse_at, ParseError};
+use std::fmt;
+use std::str::FromStr;
+
+const CAPTURE: &str = "{}";
+
+/// A parsed template, see the module documentation.
+#[derive(Debug, Clone)]
+pub struct Pattern<'t> {
+    /// The literal text before, between and after the captures.
+    /// There is always one more literal than there are captures.
+    literals: Vec<&'t str>,
+}
+
+impl<'t> Pattern<'t> {
+    /// Panics if two captures follow each other, because it would be
+    /// ambiguous where the first one ends.
+    pub fn new(template: &'t str) -> Self {
+        let literals: Vec<&str> = template.split(CAPTURE).collect();
+        let mut inner = literals
+            .iter()
+            .skip(1)
+            .take(literals.len().saturating_sub(2));
+        assert!(
+            inner.all(|literal| !literal.is_empty()),
+            "Template '{}' has two captures without text between them",
+            template
+        );
+        Pattern { literals }
+    }
+
+    /// Number of captures in the template.
+    pub fn captures_len(&self) -> usize {
+        self.literals.len() - 1
+    }
+
+    /// The captured texts of `line`, with their byte offsets in the line.
+    pub fn captures<'l>(&self, line: &'l str) -> Result<Vec<(usize, &'l str)>, ParseError> {
+        let (first, rest) = self.literals.split_first().expect("split is never empty");
+        let mut offset = expect_literal(line, 0, first)?;
+        let mut captures = vec![];
+        for (index, literal) in rest.iter().enumerate() {
+            let is_last = index + 1 == rest.len();
+            let end = if is_last && literal.is_empty() {
+                line.len()
+            } else {
+                match line[offset..].find(literal) {
+                    Some(found) => offset + found,
+                    None => {
+                        return Err(ParseError::in_line(
+                            line,
+                            offset,
+                            format!("expected '{}' after capture {}", literal, index + 1),
+                        ))
+                    }
+                }
+            };
+            if end == offset {
+                return Err(ParseError::in_line(
+                    line,
+                    offset,
+                    format!("capture {} is empty", index + 1),
+                ));
+            }
+            captures.push((offset, &line[offset..end]));
+            offset = end + literal.len();
+        }
+        if offset < line.len() {
+            return Err(ParseError::in_line(
+                line,
+                offset,
+                "unexpected text after the end of the pattern".to_string(),
+            ));
+        }
+        Ok(captures)
+    }
+
+    /// Match `line` and parse the captures, e.g. as `(i64, i64)`.
+    pub fn parse<T: FromCaptures>(&self, line: &str) -> Result<T, ParseError> {
+        let captures = self.captures(line)?;
+        if captures.len() != T::LEN {
+            return Err(ParseError::in_line(
+                line,
+                0,
+                format!(
+                    "the pattern has {} captures, but {} values are expected",
+                    captures.len(),
+                    T::LEN
+                ),
+            ));
+        }
+        T::from_captures(line, &captures)
+    }
+}
+
+/// Match `line` against `template` and parse the captures:
+///
+/// ```
+/// # use aoc2024::utils::pattern::scan;
+/// let (x, y): (i64, i64) = scan("Button A: X+{}, Y+{}", "Button A: X+94, Y+34").unwrap();
+/// assert_eq!((x, y), (94, 34));
+/// ```
+pub fn scan<T: FromCaptures>(template: &str, line: &str) -> Result<T, ParseError> {
+    Pattern::new(template).parse(line)
+}
+
+/// Check that `literal` is at byte `offset` of `line` and return the offset after it.
+fn expect_literal(line: &str, offset: usize, literal: &str) -> Result<usize, ParseError> {
+    if line[offset..].starts_with(literal) {
+        Ok(offset + literal.len())
+    } else {
+        Err(ParseError::in_line(
+            line,
+            offset,
+            format!("expected '{}'", literal),
+        ))
+    }
+}
+
+/// Values that can be built from the captures of a [Pattern]: tuples of
+/// types that implement [FromStr].
+pub trait FromCaptures: Sized {
+    /// Number of captures needed.
+    const LEN: usize;
+
+    /// Parse the captures. `captures` has exactly `LEN` elements, each with
+    /// its byte offset in `line`.
+    fn from_captures(line: &str, captures: &[(usize, &str)]) -> Result<Self, ParseError>;
+}
+
+macro_rules! from_captures_for_tuple {
+    ($len:expr; $($t:ident $index:tt),*) => {
+        impl<$($t),*> FromCaptures for ($($t,)*)
+        where
+            $($t: FromStr, $t::Err: fmt::Display),*
+        {
+            const LEN: usize = $len;
+
+            fn from_captures(line: &str, captures: &[(usize, &str)]) -> Result<Self, ParseError> {
+                Ok(($(parse_at(line, captures[$index].0, captures[$index].1)?,)*))
+            }
+        }
+    };
+}
+
+from_captures_for_tuple!(1; A 0);
+from_captures_for_tuple!(2; A 0, B 1);
+from_captures_for_tuple!(3; A 0, B 1, C 2);
+from_captures_for_tuple!(4; A 0, B 1, C 2, D 3);
+from_captures_for_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+from_captures_for_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let button = Pattern::new("Button A: X+{}, Y+{}");
+        assert_eq!(button.captures_len(), 2);
+        assert_eq!(button.parse("Button A: X+94, Y+34"), Ok((94, 34)));
+        assert_eq!(scan("p={},{} v={},{}", "p=0,4 v=3,-3"), Ok((0, 4, 3, -3)));
+        assert_eq!(scan("{}   {}", "3   4"), Ok((3u32, 4u32)));
+        assert_eq!(scan("{} -> {}", "xy -> z"), Ok(("xy".to_string(), 'z')));
+    }
+
+    #[test]
+    fn test_errors() {
+        let button = Pattern::new("Button A: X+{}, Y+{}");
+        let error = button
+            .parse::<(i64, i64)>("Button B: X+94, Y+34")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 1: expected 'Button A: X+'"
+        );
+        let error = button
+            .parse::<(i64, i64)>("Button A: X+94 Y+34")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 13: expected ', Y+' after capture 1"
+        );
+        let error = button
+            .parse::<(i64, i64)>("Button A: X+94, Y+3x")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 19: cannot parse '3x': invalid digit found in string"
+        );
+        assert_eq!(
+            button
+                .parse::<(i64,)>("Button A: X+1, Y+2")
+                .unwrap_err()
+                .column,
+            1
+        );
+        assert_eq!(
+            button
+                .parse::<(i64, i64)>("Button A: X+, Y+2")
+                .unwrap_err()
+                .column,
+            13
+        );
+        assert_eq!(scan::<(u8,)>("a{}b", "a1bc").unwrap_err().column, 4);
+        assert_eq!(
+            Pattern::new("no captures").captures("no captures"),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "two captures without text between them")]
+    fn test_adjacent_captures() {
+        Pattern::new("{}{}");
+    }
+}
@@ -1,32 +1,159 @@
 //! Module for "square character grid" utility functions.
 //! Square character grids are a common input type in Advent of Code.
+//!
+//! The grid is generic over its cells, so per-cell data like region labels,
+//! distances or visited flags can live in a [Grid] of the same shape as the
+//! input. [SquareCharacterGrid] is the grid of input characters, [ByteGrid]
+//! the smaller and faster grid of ASCII input bytes.
 
-use std::convert::TryInto;
+use super::normalize::normalize;
+use super::parse::ParseError;
 
+/// A rectangular grid of cells of type `T`, stored row by row in a single
+/// buffer. The cell at `(x, y)` has the linear index `y * w + x`.
+///
 /// This is generic over the coordinate type, because you often want to use
 /// signed integers for coordinates instead of unsigned integers.
-pub struct SquareCharacterGrid<Coord: num::PrimInt> {
-    grid: Vec<Vec<char>>,
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T, Coord: num::PrimInt> {
+    cells: Vec<T>,
     w: Coord,
     h: Coord,
 }
 
+/// A grid of the characters of the puzzle input.
+pub type SquareCharacterGrid<Coord> = Grid<char, Coord>;
+
+/// A grid of the bytes of an ASCII puzzle input, a quarter of the size of a
+/// [SquareCharacterGrid].
+pub type ByteGrid<Coord> = Grid<u8, Coord>;
+
 impl <Coord: num::PrimInt> SquareCharacterGrid<Coord> {
     /// Create a new SquareCharacterGrid from a string.
+    /// The input is normalized first, so CRLF line endings and trailing
+    /// blank lines do not end up in the grid.
+    ///
+    /// Panics if the input is not a valid grid, see [SquareCharacterGrid::try_new].
     pub fn new(input: &str) -> Self {
-        let mut grid = vec![];
-        let mut w = Coord::zero();
-        let mut h = Coord::zero();
-        for line in input.lines() {
-            let mut row = vec![];
+        Self::try_new(input).unwrap_or_else(|error| panic!("Invalid grid: {}", error))
+    }
+
+    /// Create a new SquareCharacterGrid from a string, like [SquareCharacterGrid::new].
+    /// Fails if the input is empty, if a row is not as wide as the first row,
+    /// or if the width or height does not fit into `Coord`.
+    pub fn try_new(input: &str) -> Result<Self, ParseError> {
+        Self::from_lines(input, Ok)
+    }
+
+    /// Print the grid to the console.
+    pub fn print(&self) {
+        for row in self.cells.chunks(self.width().max(1)) {
+            println!("{}", row.iter().collect::<String>());
+        }
+    }
+}
+
+impl <Coord: num::PrimInt> ByteGrid<Coord> {
+    /// Create a grid of the bytes of an ASCII input. Panics if the input is
+    /// not a valid grid, see [ByteGrid::try_from_ascii].
+    pub fn from_ascii(input: &str) -> Self {
+        Self::try_from_ascii(input).unwrap_or_else(|error| panic!("Invalid grid: {}", error))
+    }
+
+    /// Like [SquareCharacterGrid::try_new], but also fails on non-ASCII characters.
+    pub fn try_from_ascii(input: &str) -> Result<Self, ParseError> {
+        Self::from_lines(input, |c| {
+            if c.is_ascii() {
+                Ok(c as u8)
+            } else {
+                Err(format!("'{}' is not an ASCII character", c))
+            }
+        })
+    }
+}
+
+impl <T, Coord: num::PrimInt> Grid<T, Coord> {
+    /// Build a grid from the lines of a normalized input, converting every
+    /// character with `cell`. Errors of `cell` are reported at the character.
+    fn from_lines(
+        input: &str,
+        mut cell: impl FnMut(char) -> Result<T, String>,
+    ) -> Result<Self, ParseError> {
+        let input = normalize(input, "grid input");
+        let error = |line: usize, column: usize, message: String| ParseError {
+            section: None,
+            line,
+            column,
+            message,
+        };
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+        for (index, line) in input.lines().enumerate() {
+            let mut row_width = 0;
             for c in line.chars() {
-                row.push(c);
+                cells.push(cell(c).map_err(|message| error(index + 1, row_width + 1, message))?);
+                row_width += 1;
+            }
+            let width = *width.get_or_insert(row_width);
+            if row_width != width {
+                return Err(error(
+                    index + 1,
+                    row_width.min(width) + 1,
+                    format!(
+                        "row has width {}, but the first row has width {}",
+                        row_width, width
+                    ),
+                ));
+            }
+            height += 1;
+        }
+        let Some(width) = width else {
+            return Err(error(1, 1, "grid is empty".to_string()));
+        };
+        let w = Coord::from(width).ok_or_else(|| {
+            error(1, 1, format!("width {} does not fit the coordinate type", width))
+        })?;
+        let h = Coord::from(height).ok_or_else(|| {
+            error(1, 1, format!("height {} does not fit the coordinate type", height))
+        })?;
+        Ok(Self { cells, w, h })
+    }
+
+    /// Create a `w` by `h` grid with the cell at `(x, y)` set to `f(x, y)`.
+    /// Panics if the size is negative.
+    pub fn from_fn(w: Coord, h: Coord, mut f: impl FnMut(Coord, Coord) -> T) -> Self {
+        let width = w.to_usize().expect("width must not be negative");
+        let height = h.to_usize().expect("height must not be negative");
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let y = Coord::from(y).expect("y fits, it is below h");
+            for x in 0..width {
+                cells.push(f(Coord::from(x).expect("x fits, it is below w"), y));
             }
-            grid.push(row);
-            h = h + Coord::one();
-            w = Coord::from(grid[0].len()).expect("width out of bounds");
         }
-        Self { grid, w, h }
+        Self { cells, w, h }
+    }
+
+    /// Create a `w` by `h` grid with every cell set to `value`.
+    pub fn filled(w: Coord, h: Coord, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(w, h, |_, _| value.clone())
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U, Coord> {
+        self.map_with_coords(|_, _, cell| f(cell))
+    }
+
+    /// Like [Grid::map], but `f` also gets the coordinates of the cell.
+    pub fn map_with_coords<U>(
+        &self,
+        mut f: impl FnMut(Coord, Coord, &T) -> U,
+    ) -> Grid<U, Coord> {
+        Grid::from_fn(self.w, self.h, |x, y| f(x, y, self.get_ref(x, y).expect("same shape")))
     }
 
     pub fn size(&self) -> (Coord, Coord) {
@@ -34,60 +161,172 @@ impl <Coord: num::PrimInt> SquareCharacterGrid<Coord> {
     }
 
     /// Get an iterator over the grid.
-    pub fn iter(&self) -> SquareCharacterGridIterator<Coord> {
-        SquareCharacterGridIterator {
+    pub fn iter(&self) -> GridIterator<'_, T, Coord> {
+        GridIterator {
             grid: self,
+            index: 0,
             x: Coord::zero(),
             y: Coord::zero(),
         }
     }
 
-    /// Get the character at a specific coordinate.
-    pub fn get(&self, x: Coord, y: Coord) -> Option<char> {
-        if x < Coord::zero() || y < Coord::zero() {
+    fn width(&self) -> usize {
+        self.w.to_usize().expect("width is not negative")
+    }
+
+    /// The linear index of a coordinate, if it is inside the grid.
+    pub fn linear_index(&self, x: Coord, y: Coord) -> Option<usize> {
+        if x >= self.w || y >= self.h {
             return None;
         }
-        let x = x.to_usize().expect("x coordinate out of usize bounds");
-        let y = y.to_usize().expect("y coordinate out of usize bounds");
-        if x >= self.grid[0].len() || y >= self.grid.len() {
+        // Negative coordinates do not convert.
+        let (x, y) = (x.to_usize()?, y.to_usize()?);
+        Some(y * self.width() + x)
+    }
+
+    /// The coordinates of a linear index, if it is inside the grid.
+    pub fn coords_of(&self, index: usize) -> Option<(Coord, Coord)> {
+        if index >= self.cells.len() {
             return None;
         }
-        Some(self.grid[y][x])
+        let x = Coord::from(index % self.width()).expect("x fits, it is below w");
+        let y = Coord::from(index / self.width()).expect("y fits, it is below h");
+        Some((x, y))
     }
 
-    /// Set the character at a specific coordinate.
-    pub fn set(&mut self, x: Coord, y: Coord, c: char) {
-        let x = x.to_usize().expect("x coordinate out of usize bounds");
-        let y = y.to_usize().expect("y coordinate out of usize bounds");
-        self.grid[y][x] = c;
+    /// All cells in row-major order, indexed by [Grid::linear_index].
+    pub fn cells(&self) -> &[T] {
+        &self.cells
     }
 
-    /// Print the grid to the console.
-    pub fn print(&self) {
-        for row in &self.grid {
-            for c in row {
-                print!("{}", c);
-            }
-            println!();
-        }
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Get a reference to the cell at a specific coordinate.
+    pub fn get_ref(&self, x: Coord, y: Coord) -> Option<&T> {
+        let index = self.linear_index(x, y)?;
+        Some(&self.cells[index])
+    }
+
+    /// Get a mutable reference to the cell at a specific coordinate.
+    pub fn get_mut(&mut self, x: Coord, y: Coord) -> Option<&mut T> {
+        let index = self.linear_index(x, y)?;
+        Some(&mut self.cells[index])
+    }
+
+    /// Set the cell at a specific coordinate. Panics if it is outside the grid.
+    pub fn set(&mut self, x: Coord, y: Coord, value: T) {
+        *self.get_mut(x, y).expect("coordinate outside of the grid") = value;
     }
 }
 
-pub struct SquareCharacterGridIterator<'a, Coord: num::PrimInt> {
-    grid: &'a SquareCharacterGrid<Coord>,
+/// Offsets `(dx, dy)` of the four orthogonal neighbors, clockwise from up.
+pub const OFFSETS4: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets `(dx, dy)` of all eight neighbors, clockwise from up.
+pub const OFFSETS8: [(i32, i32); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+impl <T: Copy, Coord: num::PrimInt> Grid<T, Coord> {
+    /// Get the cell at a specific coordinate.
+    pub fn get(&self, x: Coord, y: Coord) -> Option<T> {
+        self.get_ref(x, y).copied()
+    }
+
+    /// The orthogonal neighbors of `(x, y)` inside the grid, as `(x, y, value)`.
+    pub fn neighbors4<'g>(
+        &'g self,
+        x: Coord,
+        y: Coord,
+    ) -> impl Iterator<Item = (Coord, Coord, T)> + 'g
+    where
+        Coord: 'g,
+    {
+        self.neighbors_with(&OFFSETS4, x, y)
+    }
+
+    /// All eight neighbors of `(x, y)` inside the grid, as `(x, y, value)`.
+    pub fn neighbors8<'g>(
+        &'g self,
+        x: Coord,
+        y: Coord,
+    ) -> impl Iterator<Item = (Coord, Coord, T)> + 'g
+    where
+        Coord: 'g,
+    {
+        self.neighbors_with(&OFFSETS8, x, y)
+    }
+
+    /// The cells at the given `(dx, dy)` offsets from `(x, y)` that are inside
+    /// the grid, as `(x, y, value)`.
+    pub fn neighbors_with<'g>(
+        &'g self,
+        offsets: &'g [(i32, i32)],
+        x: Coord,
+        y: Coord,
+    ) -> impl Iterator<Item = (Coord, Coord, T)> + 'g
+    where
+        Coord: 'g,
+    {
+        self.directed_neighbors_with(offsets, x, y)
+            .map(|(_, x, y, value)| (x, y, value))
+    }
+
+    /// Like [Grid::neighbors_with], but also yields the offset each neighbor
+    /// was reached by, as `((dx, dy), x, y, value)`.
+    pub fn directed_neighbors_with<'g>(
+        &'g self,
+        offsets: &'g [(i32, i32)],
+        x: Coord,
+        y: Coord,
+    ) -> impl Iterator<Item = ((i32, i32), Coord, Coord, T)> + 'g
+    where
+        Coord: 'g,
+    {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let (nx, ny) = (offset(x, dx)?, offset(y, dy)?);
+            let value = self.get(nx, ny)?;
+            Some(((dx, dy), nx, ny, value))
+        })
+    }
+}
+
+/// `c + d`, or `None` if that does not fit into `Coord`.
+fn offset<Coord: num::PrimInt>(c: Coord, d: i32) -> Option<Coord> {
+    let magnitude = Coord::from(d.unsigned_abs())?;
+    if d < 0 {
+        c.checked_sub(&magnitude)
+    } else {
+        c.checked_add(&magnitude)
+    }
+}
+
+pub struct GridIterator<'a, T, Coord: num::PrimInt> {
+    grid: &'a Grid<T, Coord>,
+    index: usize,
     x: Coord,
     y: Coord,
 }
 
-impl <'a, Coord: num::PrimInt> Iterator for SquareCharacterGridIterator<'a, Coord> {
-    type Item = (Coord, Coord, char);
+/// The iterator over a [SquareCharacterGrid].
+pub type SquareCharacterGridIterator<'a, Coord> = GridIterator<'a, char, Coord>;
+
+impl <'a, T: Copy, Coord: num::PrimInt> Iterator for GridIterator<'a, T, Coord> {
+    type Item = (Coord, Coord, T);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.y >= self.grid.h {
-            return None;
-        }
-        let c = self.grid.get(self.x, self.y).expect("iterator out of bounds");
+        let c = *self.grid.cells.get(self.index)?;
         let result = (self.x, self.y, c);
+        self.index += 1;
 
         // Step one forward, check for linebreaks.
         self.x = self.x + Coord::one();
@@ -121,6 +360,118 @@ mod tests {
         assert_eq!(grid.h, 3);
     }
 
+    #[test]
+    fn test_try_new() {
+        let error = SquareCharacterGrid::<i32>::try_new("..#\n#.\n...").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: row has width 2, but the first row has width 3"
+        );
+        let error = SquareCharacterGrid::<i32>::try_new("\n\n").err().unwrap();
+        assert_eq!(error.message, "grid is empty");
+        let wide = ".".repeat(200);
+        let error = SquareCharacterGrid::<i8>::try_new(&wide).err().unwrap();
+        assert_eq!(error.message, "width 200 does not fit the coordinate type");
+        let tall = "#\n".repeat(128);
+        let error = SquareCharacterGrid::<i8>::try_new(&tall).err().unwrap();
+        assert_eq!(error.message, "height 128 does not fit the coordinate type");
+        assert!(SquareCharacterGrid::<i8>::try_new(&"#\n".repeat(127)).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid grid: line 2")]
+    fn test_new_ragged() {
+        SquareCharacterGrid::<i32>::new("..\n.\n");
+    }
+
+    #[test]
+    fn test_generic_grid() {
+        let distances: Grid<u32, i16> = Grid::from_fn(3, 2, |x, y| (x + 10 * y) as u32);
+        assert_eq!(distances.size(), (3, 2));
+        assert_eq!(distances.get(2, 1), Some(12));
+        assert_eq!(distances.get(3, 1), None);
+        assert_eq!(distances.get(-1, 0), None);
+
+        let mut visited = Grid::filled(3i16, 2i16, false);
+        visited.set(1, 1, true);
+        assert_eq!(visited.iter().filter(|(_, _, seen)| *seen).count(), 1);
+        *visited.get_mut(0, 0).unwrap() = true;
+        assert_eq!(visited.get(0, 0), Some(true));
+
+        let chars = SquareCharacterGrid::<i16>::new("ab\ncd");
+        let labels = chars.map_with_coords(|x, y, c| format!("{}{}{}", c, x, y));
+        assert_eq!(labels.get_ref(1, 1).map(String::as_str), Some("d11"));
+        let upper = chars.map(|c| c.to_ascii_uppercase());
+        assert_eq!(upper.iter().map(|(_, _, c)| c).collect::<String>(), "ABCD");
+
+        let empty: Grid<u8, i32> = Grid::filled(0, 5, 0);
+        assert_eq!(empty.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_linear_index() {
+        let grid: SquareCharacterGrid<i32> = SquareCharacterGrid::new("abc\ndef");
+        assert_eq!(grid.cells(), ['a', 'b', 'c', 'd', 'e', 'f']);
+        assert_eq!(grid.linear_index(1, 1), Some(4));
+        assert_eq!(grid.linear_index(3, 0), None);
+        assert_eq!(grid.coords_of(4), Some((1, 1)));
+        assert_eq!(grid.coords_of(6), None);
+        let mut grid = grid;
+        grid.cells_mut()[5] = 'F';
+        assert_eq!(grid.get(2, 1), Some('F'));
+    }
+
+    #[test]
+    fn test_byte_grid() {
+        let grid: ByteGrid<i16> = ByteGrid::from_ascii("..#\n#..\n");
+        assert_eq!(grid.size(), (3, 2));
+        assert_eq!(grid.get(2, 0), Some(b'#'));
+        assert_eq!(grid.iter().filter(|(_, _, c)| *c == b'#').count(), 2);
+        let error = ByteGrid::<i16>::try_from_ascii("..\n.\u{e9}\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: '\u{e9}' is not an ASCII character"
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid: SquareCharacterGrid<i32> = SquareCharacterGrid::new("abc\ndef\nghi");
+        let values = |neighbors: &mut dyn Iterator<Item = (i32, i32, char)>| {
+            neighbors.map(|(_, _, c)| c).collect::<String>()
+        };
+        assert_eq!(values(&mut grid.neighbors4(1, 1)), "bfhd");
+        assert_eq!(values(&mut grid.neighbors8(1, 1)), "bcfihgda");
+        assert_eq!(values(&mut grid.neighbors4(0, 0)), "bd");
+        assert_eq!(
+            grid.neighbors8(2, 2).collect::<Vec<_>>(),
+            [(2, 1, 'f'), (1, 2, 'h'), (1, 1, 'e')]
+        );
+
+        let knight = [(1, 2), (2, 1), (-1, -2)];
+        assert_eq!(values(&mut grid.neighbors_with(&knight, 0, 0)), "hf");
+        let directed: Vec<_> = grid.directed_neighbors_with(&OFFSETS4, 0, 1).collect();
+        assert_eq!(
+            directed,
+            [((0, -1), 0, 0, 'a'), ((1, 0), 1, 1, 'e'), ((0, 1), 0, 2, 'g')]
+        );
+
+        // Unsigned coordinates cannot go below zero.
+        let grid: SquareCharacterGrid<u8> = SquareCharacterGrid::new("ab\ncd");
+        assert_eq!(grid.neighbors8(0, 0).count(), 3);
+    }
+
+    #[test]
+    fn test_normalized_input() {
+        let grid: SquareCharacterGrid<i32> = SquareCharacterGrid::new("..#\r\n#..\r\n\r\n");
+        assert_eq!(grid.size(), (3, 2));
+        assert_eq!(grid.get(2, 0), Some('#'));
+        // Spaces in the last row are cells, not part of a blank line.
+        let grid: SquareCharacterGrid<i32> = SquareCharacterGrid::new("a \nb \n\n");
+        assert_eq!(grid.size(), (2, 2));
+        assert_eq!(grid.get(1, 1), Some(' '));
+    }
+
     #[test]
     fn test_iterator() {
         let input = "..#\n#..\n...";
@@ -0,0 +1,93 @@
+//! Re-runs a day whenever one of its input files changes.
+//!
+//! This polls modification times and sizes instead of using a platform
+//! specific file notifier, which is plenty fast for a handful of files.
+
+use crate::inputs::{self, InputFile, InputSelection};
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Path, modification time and size of every watched file.
+#[derive(Debug, PartialEq)]
+struct Snapshot(Vec<(PathBuf, Option<SystemTime>, Option<u64>)>);
+
+impl Snapshot {
+    fn take(files: &[InputFile]) -> Self {
+        Snapshot(
+            files
+                .iter()
+                .map(|file| {
+                    let metadata = fs::metadata(&file.path).ok();
+                    let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+                    let len = metadata.map(|m| m.len());
+                    (file.path.clone(), modified, len)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Call `on_change` with the selected input files once at the start, and then
+/// again every time a file changes, appears or disappears. Never returns.
+pub fn watch<F: FnMut(&[InputFile])>(
+    day: u8,
+    selection: &InputSelection,
+    interval: Duration,
+    mut on_change: F,
+) -> ! {
+    let mut last_snapshot = None;
+    let mut last_error = None;
+    loop {
+        // Select again on every poll, so new example files are picked up.
+        match inputs::select(day, selection) {
+            Ok(files) => {
+                last_error = None;
+                let snapshot = Snapshot::take(&files);
+                if last_snapshot.as_ref() != Some(&snapshot) {
+                    on_change(&files);
+                    last_snapshot = Some(snapshot);
+                }
+            }
+            Err(error) => {
+                let message = error.to_string();
+                if last_error.as_ref() != Some(&message) {
+                    eprintln!("Error: {}", message);
+                    last_error = Some(message);
+                }
+                last_snapshot = None;
+            }
+        }
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::InputKind;
+    use crate::utils::TempPath;
+
+    #[test]
+    fn test_snapshot_sees_changes() {
+        let path = TempPath::new("watch");
+        let files = [InputFile {
+            day: 11,
+            name: "watched".to_string(),
+            kind: InputKind::Other,
+            path: path.to_path_buf(),
+        }];
+
+        let missing = Snapshot::take(&files);
+        fs::write(&path, "1 2 3").unwrap();
+        let written = Snapshot::take(&files);
+        assert_ne!(missing, written);
+        assert_eq!(written, Snapshot::take(&files));
+
+        fs::write(&path, "1 2 3 4").unwrap();
+        assert_ne!(written, Snapshot::take(&files));
+    }
+}
@@ -0,0 +1,29 @@
+//! Day {day}
+
+use crate::solution::{Answer, Solution};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    fn part1(&self, input: &str) -> Answer {
+        part1(input).into()
+    }
+}
+
+fn part1(input: &str) -> usize {
+    // Replace with the actual puzzle solution.
+    input.lines().count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{crate_root, read_path};
+
+    #[test]
+    fn test_example() {
+        // Copy the example from the puzzle text to input/day{day}-test and fill in its answer.
+        let input = read_path(&crate_root().join("input/day{day}-test"), Some({day})).unwrap();
+        assert_eq!(part1(&input), 0);
+    }
+}
@@ -0,0 +1,22 @@
+//! The utilities and solutions are usable from outside the crate.
+
+use aoc2024::registry;
+use aoc2024::utils::square_grid::SquareCharacterGrid;
+use aoc2024::utils::{crate_root, read_path};
+
+#[test]
+fn test_square_grid_from_library() {
+    let grid: SquareCharacterGrid<i32> = SquareCharacterGrid::new("AB\nCD");
+    assert_eq!(grid.size(), (2, 2));
+    assert_eq!(grid.get(1, 1), Some('D'));
+}
+
+#[test]
+fn test_solution_from_library() {
+    let day = registry::find_day(12).unwrap();
+    let input = read_path(&crate_root().join("input/day12-test-a"), Some(12)).unwrap();
+    assert_eq!(
+        day.solution.solve(1, &input).unwrap().unwrap().to_string(),
+        "140"
+    );
+}